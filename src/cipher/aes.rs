@@ -14,6 +14,8 @@ pub enum CipherError {
     InvalidPadding(#[fail(cause)] InvalidPaddingError),
     #[fail(display = "{}", _0)]
     OpenSslError(#[fail(cause)] openssl::error::ErrorStack),
+    #[fail(display = "Plaintext contains high-ASCII bytes: {:?}", _0)]
+    InvalidAscii(Vec<u8>),
 }
 
 impl From<InvalidPaddingError> for CipherError {
@@ -74,6 +76,12 @@ fn aes_128_ecb(mode: Mode, key: &[u8], data: &[u8]) -> Result<Vec<u8>, ErrorStac
     Ok(out)
 }
 
+// encrypt a single block, without any padding
+pub fn encrypt_block(key: &[u8], block: &[u8]) -> Result<Vec<u8>, CipherError> {
+    assert_eq!(block.len(), AES_BLOCK_SIZE);
+    aes_128_ecb(Mode::Encrypt, key, block).map_err(CipherError::from)
+}
+
 pub fn ecb_encrypt(key: &[u8], data: &[u8]) -> Result<Vec<u8>, CipherError> {
     let padded_data = pkcs7_pad(data, AES_BLOCK_SIZE);
     aes_128_ecb(Mode::Encrypt, key, &padded_data).map_err(CipherError::from)
@@ -128,25 +136,62 @@ pub fn cbc_decrypt(key: &[u8], iv: &[u8], msg: &[u8]) -> Result<Vec<u8>, CipherE
     return Ok(decrypted_msg);
 }
 
-pub fn ctr_cipher(key: &[u8], nonce: u64, cyphertext: &[u8]) -> Vec<u8> {
+// keystream for AES in CTR mode, starting `offset` bytes into the stream
+pub fn ctr_keystream(key: &[u8], nonce: u64, offset: usize, len: usize) -> Vec<u8> {
     let nonce_bytes = nonce.to_le_bytes();
 
-    cyphertext
-        .chunks(AES_BLOCK_SIZE)
-        .enumerate()
-        .flat_map(|(i, chunk)| {
+    let first_block = offset / AES_BLOCK_SIZE;
+    let last_block = (offset + len).div_ceil(AES_BLOCK_SIZE);
+
+    let keystream = (first_block..last_block)
+        .flat_map(|i| {
             let mut keystream_input = nonce_bytes.to_vec();
             keystream_input.append(&mut (i as u64).to_le_bytes().to_vec());
 
-            let keystream = ecb_encrypt(key, &keystream_input).unwrap();
+            let mut keystream_block = ecb_encrypt(key, &keystream_input).unwrap();
+            keystream_block.truncate(AES_BLOCK_SIZE);
 
-            let encrypted_chunk: Vec<u8> = chunk
-                .iter()
-                .enumerate()
-                .map(|(i, byte)| byte ^ keystream[i])
-                .collect();
-
-            encrypted_chunk
+            keystream_block
         })
+        .collect::<Vec<u8>>();
+
+    let skip = offset % AES_BLOCK_SIZE;
+    keystream[skip..skip + len].to_vec()
+}
+
+pub fn ctr_cipher(key: &[u8], nonce: u64, cyphertext: &[u8]) -> Vec<u8> {
+    let keystream = ctr_keystream(key, nonce, 0, cyphertext.len());
+
+    cyphertext
+        .iter()
+        .zip(keystream)
+        .map(|(byte, k)| byte ^ k)
         .collect::<Vec<u8>>()
 }
+
+// re-encrypt part of a CTR cyphertext, replacing the plaintext
+// starting at `offset` with `newtext`. The cyphertext grows if
+// newtext runs past its end, but there's no filling in a gap, so
+// an offset past the end gives None
+pub fn ctr_edit(
+    key: &[u8],
+    nonce: u64,
+    cyphertext: &[u8],
+    offset: usize,
+    newtext: &[u8],
+) -> Option<Vec<u8>> {
+    if offset > cyphertext.len() {
+        return None;
+    }
+
+    let keystream = ctr_keystream(key, nonce, offset, newtext.len());
+
+    let mut edited = cyphertext[..offset].to_vec();
+    edited.extend(newtext.iter().zip(keystream).map(|(byte, k)| byte ^ k));
+
+    if offset + newtext.len() < cyphertext.len() {
+        edited.extend(&cyphertext[offset + newtext.len()..]);
+    }
+
+    Some(edited)
+}
@@ -1,2 +1,3 @@
 pub mod xor;
 pub mod aes;
+pub mod mt19937;
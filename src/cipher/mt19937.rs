@@ -0,0 +1,132 @@
+// 32-bit Mersenne Twister, as described in Matsumoto & Nishimura's
+// reference implementation (mt19937ar.c)
+
+pub const N: usize = 624;
+const M: usize = 397;
+const MATRIX_A: u32 = 0x9908_b0df;
+const UPPER_MASK: u32 = 0x8000_0000;
+const LOWER_MASK: u32 = 0x7fff_ffff;
+
+#[derive(Clone, Debug)]
+pub struct MT19937 {
+    state: [u32; N],
+    index: usize,
+}
+
+pub fn temper(y: u32) -> u32 {
+    let mut y = y;
+    y ^= y >> 11;
+    y ^= (y << 7) & 0x9d2c_5680;
+    y ^= (y << 15) & 0xefc6_0000;
+    y ^= y >> 18;
+    y
+}
+
+impl MT19937 {
+    pub fn seed(seed: u32) -> MT19937 {
+        let mut state = [0u32; N];
+        state[0] = seed;
+
+        for i in 1..N {
+            state[i] = 1_812_433_253u32
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+
+        MT19937 { state, index: N }
+    }
+
+    // init_by_array from the reference implementation, which needs at
+    // least one word of key
+    pub fn seed_by_array(key: &[u32]) -> MT19937 {
+        assert!(!key.is_empty(), "MT19937 can't be seeded with an empty key");
+
+        let mut mt = MT19937::seed(19_650_218);
+        let state = &mut mt.state;
+
+        let mut i = 1;
+        let mut j = 0;
+
+        for _ in 0..N.max(key.len()) {
+            state[i] = (state[i] ^ (state[i - 1] ^ (state[i - 1] >> 30)).wrapping_mul(1_664_525))
+                .wrapping_add(key[j])
+                .wrapping_add(j as u32);
+            i += 1;
+            j += 1;
+            if i >= N {
+                state[0] = state[N - 1];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+
+        for _ in 0..N - 1 {
+            state[i] = (state[i]
+                ^ (state[i - 1] ^ (state[i - 1] >> 30)).wrapping_mul(1_566_083_941))
+            .wrapping_sub(i as u32);
+            i += 1;
+            if i >= N {
+                state[0] = state[N - 1];
+                i = 1;
+            }
+        }
+
+        // MSB is 1, assuring a non-zero initial state
+        state[0] = 0x8000_0000;
+
+        mt
+    }
+
+    // builds a generator from a raw (untempered) state whose words have
+    // all been handed out already, so the next call to next_u32 twists it
+    pub fn from_state(state: [u32; N]) -> MT19937 {
+        MT19937 { state, index: N }
+    }
+
+    pub fn state(&self) -> &[u32; N] {
+        &self.state
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        if self.index >= N {
+            self.twist();
+        }
+
+        let y = self.state[self.index];
+        self.index += 1;
+
+        temper(y)
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N {
+            let y = (self.state[i] & UPPER_MASK) | (self.state[(i + 1) % N] & LOWER_MASK);
+            let mag = if y & 1 == 0 { 0 } else { MATRIX_A };
+
+            self.state[i] = self.state[(i + M) % N] ^ (y >> 1) ^ mag;
+        }
+
+        self.index = 0;
+    }
+}
+
+// stream cipher that uses the outputs of an MT19937 generator,
+// seeded with a 16-bit key, as its keystream
+pub fn mt19937_cipher(seed: u16, cyphertext: &[u8]) -> Vec<u8> {
+    let mut mt = MT19937::seed(seed as u32);
+
+    cyphertext
+        .chunks(4)
+        .flat_map(|chunk| {
+            let keystream = mt.next_u32().to_le_bytes();
+
+            chunk
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| byte ^ keystream[i])
+                .collect::<Vec<u8>>()
+        })
+        .collect::<Vec<u8>>()
+}
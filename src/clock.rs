@@ -0,0 +1,47 @@
+use std::cell::Cell;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// source of UNIX timestamps (in seconds). Oracles that depend on
+// "the current time" take a Clock, so tests can swap in a MockClock
+// rather than actually waiting around
+pub trait Clock {
+    fn now(&self) -> u32;
+    fn sleep(&self, secs: u32);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u32 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time should be after the UNIX epoch")
+            .as_secs() as u32
+    }
+
+    fn sleep(&self, secs: u32) {
+        thread::sleep(Duration::from_secs(secs as u64));
+    }
+}
+
+// a clock that only moves forward when someone sleeps on it
+pub struct MockClock {
+    now: Cell<u32>,
+}
+
+impl MockClock {
+    pub fn new(now: u32) -> MockClock {
+        MockClock { now: Cell::new(now) }
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> u32 {
+        self.now.get()
+    }
+
+    fn sleep(&self, secs: u32) {
+        self.now.set(self.now.get() + secs);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::cipher::aes::{cbc_encrypt, ctr_cipher, CipherError, AES_BLOCK_SIZE};
+use flate2::{Compress, Compression, FlushCompress, Status};
+use rand::Rng;
+use std::cell::RefCell;
+
+pub fn format_request(session_id: &str, payload: &[u8]) -> Vec<u8> {
+    let mut request = format!(
+        "POST / HTTP/1.1\r\n\
+         Host: hapless.com\r\n\
+         Cookie: sessionid={}\r\n\
+         Content-Length: {}\r\n\r\n",
+        session_id,
+        payload.len()
+    )
+    .into_bytes();
+    request.extend(payload);
+    request
+}
+
+// zlib compress, reusing the compressor's (sizeable) state between calls
+fn compress(compressor: &RefCell<Compress>, data: &[u8]) -> Vec<u8> {
+    let mut compressor = compressor.borrow_mut();
+    compressor.reset();
+
+    // compress_vec only writes into the spare capacity, so keep making
+    // more until the stream's been finished
+    let mut compressed = Vec::with_capacity(data.len() + 64);
+    loop {
+        let consumed = compressor.total_in() as usize;
+        let status = compressor
+            .compress_vec(&data[consumed..], &mut compressed, FlushCompress::Finish)
+            .unwrap();
+
+        if status == Status::StreamEnd {
+            return compressed;
+        }
+        compressed.reserve(compressed.capacity());
+    }
+}
+
+// a client that wraps attacker-supplied payloads in a request carrying
+// its session cookie, then compresses and encrypts the request (under a
+// fresh key each time). All the attacker gets to see is the length of
+// what goes over the wire
+pub trait CompressionOracle {
+    fn encrypted_len(&self, payload: &[u8]) -> Result<usize, CipherError>;
+}
+
+pub struct CtrCompressionClient {
+    session_id: String,
+    compressor: RefCell<Compress>,
+}
+
+impl CtrCompressionClient {
+    pub fn new(session_id: &str) -> CtrCompressionClient {
+        CtrCompressionClient {
+            session_id: session_id.to_owned(),
+            compressor: RefCell::new(Compress::new(Compression::default(), true)),
+        }
+    }
+}
+
+impl CompressionOracle for CtrCompressionClient {
+    fn encrypted_len(&self, payload: &[u8]) -> Result<usize, CipherError> {
+        let mut rng = rand::thread_rng();
+        let key: Vec<u8> = (0..AES_BLOCK_SIZE).map(|_| rng.gen::<u8>()).collect();
+
+        let compressed = compress(&self.compressor, &format_request(&self.session_id, payload));
+        Ok(ctr_cipher(&key, rng.gen::<u64>(), &compressed).len())
+    }
+}
+
+pub struct CbcCompressionClient {
+    session_id: String,
+    compressor: RefCell<Compress>,
+}
+
+impl CbcCompressionClient {
+    pub fn new(session_id: &str) -> CbcCompressionClient {
+        CbcCompressionClient {
+            session_id: session_id.to_owned(),
+            compressor: RefCell::new(Compress::new(Compression::default(), true)),
+        }
+    }
+}
+
+impl CompressionOracle for CbcCompressionClient {
+    fn encrypted_len(&self, payload: &[u8]) -> Result<usize, CipherError> {
+        let mut rng = rand::thread_rng();
+        let key: Vec<u8> = (0..AES_BLOCK_SIZE).map(|_| rng.gen::<u8>()).collect();
+        let iv: Vec<u8> = (0..AES_BLOCK_SIZE).map(|_| rng.gen::<u8>()).collect();
+
+        let compressed = compress(&self.compressor, &format_request(&self.session_id, payload));
+        Ok(cbc_encrypt(&key, &iv, &compressed)?.len())
+    }
+}
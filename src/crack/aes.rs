@@ -1,4 +1,5 @@
 use crate::cipher::aes::{cbc_encrypt, ecb_encrypt, pkcs7_unpad, AES_BLOCK_SIZE, CipherError};
+use crate::utils::ByteArray;
 use itertools::Itertools;
 use failure::{Error, format_err, ResultExt};
 use rand::prelude::StdRng;
@@ -332,3 +333,56 @@ pub fn crack_aes_cbc(
 
     Ok(plaintext)
 }
+
+
+// recover the plaintext behind a CTR cyphertext, given access to an `edit`
+// function (cyphertext, offset, newtext) that re-encrypts part of it under
+// the same key and nonce. Writing zeros over the whole message
+// hands us the keystream itself
+pub fn crack_ctr_edit(
+    cyphertext: &[u8],
+    edit: impl Fn(&[u8], usize, &[u8]) -> Vec<u8>,
+) -> Vec<u8> {
+    let keystream = edit(cyphertext, 0, &vec![0; cyphertext.len()]);
+
+    let ByteArray(plaintext) = ByteArray(cyphertext.to_vec()) ^ ByteArray(keystream);
+
+    plaintext
+}
+
+
+// recover the key of a CBC cypher that uses its key as the IV, given
+// a cyphertext of at least 5 blocks, and a receiver that leaks the
+// plaintext in a CipherError::InvalidAscii whenever it decrypts junk.
+//
+// Sending C_1, 0, C_1 (followed by the original trailing blocks, to keep
+// the padding valid) decrypts to P'_1, junk, P'_3, where
+// P'_1 = D(C_1) ^ key and P'_3 = D(C_1) ^ 0, so key = P'_1 ^ P'_3
+pub fn crack_cbc_key_as_iv<T>(
+    cyphertext: &[u8],
+    receiver: impl Fn(&[u8]) -> Result<T, CipherError>,
+) -> Result<Vec<u8>, Error> {
+    if cyphertext.len() < 5 * AES_BLOCK_SIZE {
+        return Err(format_err!(
+            "Need a cyphertext of at least 5 blocks, got {} bytes",
+            cyphertext.len()
+        ));
+    }
+
+    let first_block = &cyphertext[..AES_BLOCK_SIZE];
+
+    let mut modified = first_block.to_vec();
+    modified.extend(&[0; AES_BLOCK_SIZE]);
+    modified.extend(first_block);
+    modified.extend(&cyphertext[3 * AES_BLOCK_SIZE..]);
+
+    match receiver(&modified) {
+        Err(CipherError::InvalidAscii(plaintext)) => {
+            let ByteArray(key) = ByteArray(plaintext[..AES_BLOCK_SIZE].to_vec())
+                ^ ByteArray(plaintext[2 * AES_BLOCK_SIZE..3 * AES_BLOCK_SIZE].to_vec());
+            Ok(key)
+        }
+        Err(e) => Err(format_err!("Receiver rejected modified cyphertext: {}", e)),
+        Ok(_) => Err(format_err!("Receiver did not leak the plaintext")),
+    }
+}
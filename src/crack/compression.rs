@@ -0,0 +1,106 @@
+use crate::cipher::aes::CipherError;
+use crate::compression_oracle::CompressionOracle;
+
+// bytes that don't turn up in the request, so don't compress
+const SEPARATOR: &[u8] = b"!@#$%^*";
+// enough (distinct, so incompressible) filler to push the compressed
+// request across a couple of AES block boundaries
+const MAX_FILLER_LEN: u8 = 32;
+// each filler byte adds about a byte to the compressed request, whereas
+// a common letter costs fewer bits, so these shift it by a fraction
+const NUDGES: &[&[u8]] = &[b"", b"e", b"ee"];
+
+// recover the secret following `known` in the request (e.g. the value
+// of "sessionid="), a byte at a time, from the compressed and encrypted
+// length alone: the payload `known || guess` compresses best when the
+// guess is right, as it repeats more of the request.
+//
+// A single byte of difference is easily lost, whether to rounding in
+// the compressor's bit output or to block cipher padding, so the guesses
+// are tried behind increasing amounts of filler until exactly one of
+// them wins. Stops once `terminator` is recovered (which is dropped), or
+// returns None if no guess ever wins outright
+pub fn crack_compression_oracle(
+    oracle: &dyn CompressionOracle,
+    known: &[u8],
+    charset: &[u8],
+    terminator: u8,
+    max_len: usize,
+) -> Result<Option<Vec<u8>>, CipherError> {
+    let mut secret = Vec::new();
+
+    while secret.len() < max_len {
+        match crack_next_byte(oracle, known, &secret, charset, terminator)? {
+            Some(byte) if byte == terminator => return Ok(Some(secret)),
+            Some(byte) => secret.push(byte),
+            None => return Ok(None),
+        }
+    }
+
+    Ok(None)
+}
+
+fn crack_next_byte(
+    oracle: &dyn CompressionOracle,
+    known: &[u8],
+    secret: &[u8],
+    charset: &[u8],
+    terminator: u8,
+) -> Result<Option<u8>, CipherError> {
+    let charset: Vec<u8> = charset.iter().cloned().chain(Some(terminator)).collect();
+
+    let guesses: Vec<Vec<u8>> = charset.iter().map(|&c| vec![c]).collect();
+    if let Some(byte) = find_winning_guess(oracle, known, secret, &guesses)? {
+        return Ok(Some(byte));
+    }
+
+    // a right guess sometimes saves less than a byte, however it's
+    // padded out, in which case guessing two bytes at once saves more
+    let guesses: Vec<Vec<u8>> = charset
+        .iter()
+        .flat_map(|&c| charset.iter().map(move |&d| vec![c, d]))
+        .collect();
+    find_winning_guess(oracle, known, secret, &guesses)
+}
+
+// the first byte of the guess(es) that compress better attached to the
+// known part of the secret than detached from it, so long as there's
+// only one such byte
+fn find_winning_guess(
+    oracle: &dyn CompressionOracle,
+    known: &[u8],
+    secret: &[u8],
+    guesses: &[Vec<u8>],
+) -> Result<Option<u8>, CipherError> {
+    for (filler_len, nudge) in (0..=MAX_FILLER_LEN).flat_map(|f| NUDGES.iter().map(move |n| (f, n)))
+    {
+        let mut prefix: Vec<u8> = (0x80..0x80 + filler_len).collect();
+        prefix.extend(*nudge);
+        prefix.extend(known);
+        prefix.extend(secret);
+
+        let mut winners = Vec::new();
+
+        for guess in guesses {
+            // the guess compresses along with the rest of the match, or
+            // not at all, but both payloads are otherwise identical
+            let mut attached = prefix.clone();
+            attached.extend(guess);
+            attached.extend(SEPARATOR);
+            let mut detached = prefix.clone();
+            detached.extend(SEPARATOR);
+            detached.extend(guess);
+
+            if oracle.encrypted_len(&attached)? < oracle.encrypted_len(&detached)? {
+                winners.push(guess[0]);
+            }
+        }
+
+        winners.dedup();
+        if winners.len() == 1 {
+            return Ok(Some(winners[0]));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,120 @@
+use crate::protocol::dh_echo::Message;
+use crate::protocol::transport::Endpoint;
+use crate::pubkey::dh::decrypt_message;
+use failure::Error;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction {
+    ToResponder,
+    ToInitiator,
+}
+
+// sit between the initiator A (on `initiator`) and the responder B (on
+// `responder`), passing each message through `tamper` on its way across,
+// until A hangs up. The protocol is strictly request/response, so
+// messages simply alternate A -> B, B -> A
+pub fn relay(
+    initiator: Endpoint<Message>,
+    responder: Endpoint<Message>,
+    mut tamper: impl FnMut(Direction, Message) -> Result<Message, Error>,
+) -> Result<(), Error> {
+    while let Some(request) = initiator.next_message() {
+        responder.send(tamper(Direction::ToResponder, request)?)?;
+
+        let response = responder.recv()?;
+        initiator.send(tamper(Direction::ToInitiator, response)?)?;
+    }
+
+    Ok(())
+}
+
+// swap both public keys for p, so both sides end up with a shared
+// secret of p^x mod p = 0, then read everything A sends
+pub fn key_fixing_mitm(
+    initiator: Endpoint<Message>,
+    responder: Endpoint<Message>,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let mut p = BigUint::zero();
+    let mut intercepted = Vec::new();
+
+    relay(initiator, responder, |direction, msg| match msg {
+        Message::Negotiate { p: ref group_p, .. } => {
+            p = group_p.clone();
+            Ok(msg)
+        }
+        Message::PublicKey(_) => Ok(Message::PublicKey(p.clone())),
+        Message::Data(ref cyphertext) => {
+            if direction == Direction::ToResponder {
+                intercepted.push(decrypt_message(&BigUint::zero(), cyphertext)?);
+            }
+            Ok(msg)
+        }
+        msg => Ok(msg),
+    })?;
+
+    Ok(intercepted)
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MaliciousG {
+    One,
+    P,
+    PMinusOne,
+}
+
+// replace g in the group B is offered (and so acknowledges back to A),
+// which leaves the shared secret with only one or two possible values,
+// then read everything A sends
+pub fn malicious_g_mitm(
+    initiator: Endpoint<Message>,
+    responder: Endpoint<Message>,
+    malicious_g: MaliciousG,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let mut p = BigUint::zero();
+    let mut public_keys = Vec::new();
+    let mut intercepted = Vec::new();
+
+    relay(initiator, responder, |direction, msg| match msg {
+        Message::Negotiate { p: group_p, .. } => {
+            let g = match malicious_g {
+                MaliciousG::One => BigUint::one(),
+                MaliciousG::P => group_p.clone(),
+                MaliciousG::PMinusOne => &group_p - 1u32,
+            };
+            p = group_p.clone();
+            Ok(Message::Negotiate { p: group_p, g })
+        }
+        Message::PublicKey(ref public) => {
+            public_keys.push(public.clone());
+            Ok(msg)
+        }
+        Message::Data(ref cyphertext) => {
+            // g = 1: s = 1^ab = 1
+            // g = p: s = 0^ab = 0
+            // g = p - 1: s = (-1)^ab, which is -1 only if both a and b
+            // are odd, in which case both public keys are -1 too
+            let secret = match malicious_g {
+                MaliciousG::One => BigUint::one(),
+                MaliciousG::P => BigUint::zero(),
+                MaliciousG::PMinusOne => {
+                    let minus_one = &p - 1u32;
+                    if public_keys.iter().all(|public| *public == minus_one) {
+                        minus_one
+                    } else {
+                        BigUint::one()
+                    }
+                }
+            };
+
+            if direction == Direction::ToResponder {
+                intercepted.push(decrypt_message(&secret, cyphertext)?);
+            }
+            Ok(msg)
+        }
+        msg => Ok(msg),
+    })?;
+
+    Ok(intercepted)
+}
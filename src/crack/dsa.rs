@@ -0,0 +1,131 @@
+use crate::pubkey::dsa::{message_hash, DsaParams, DsaSignature};
+use crate::pubkey::math::{invmod, modexp};
+use crate::utils::read_lines;
+use failure::{format_err, Error};
+use itertools::Itertools;
+use num_bigint::BigUint;
+
+// s = k^-1 (H(m) + xr) mod q, so knowing k gives away
+// x = (sk - H(m)) r^-1 mod q
+pub fn x_from_nonce(
+    params: &DsaParams,
+    msg_hash: &BigUint,
+    signature: &DsaSignature,
+    k: &BigUint,
+) -> Option<BigUint> {
+    let q = &params.q;
+    let r_inv = invmod(&signature.r, q)?;
+
+    // add a multiple of q before subtracting, to stay positive
+    let sk = &signature.s * k % q;
+    Some((sk + q - msg_hash % q) * r_inv % q)
+}
+
+// find the private key behind `signature`, if its nonce was below `max_k`,
+// by checking r against g^k for each k in turn
+pub fn brute_force_nonce(
+    params: &DsaParams,
+    y: &BigUint,
+    msg_hash: &BigUint,
+    signature: &DsaSignature,
+    max_k: u32,
+) -> Option<BigUint> {
+    let mut g_k = BigUint::from(1u32);
+
+    for k in 1..=max_k {
+        g_k = g_k * &params.g % &params.p;
+
+        if &g_k % &params.q != signature.r {
+            continue;
+        }
+
+        let x = x_from_nonce(params, msg_hash, signature, &BigUint::from(k))?;
+        if modexp(&params.g, &x, &params.p) == *y {
+            return Some(x);
+        }
+    }
+
+    None
+}
+
+#[derive(Clone, Debug)]
+pub struct SignedMessage {
+    pub msg: String,
+    pub hash: BigUint,
+    pub signature: DsaSignature,
+}
+
+// reads a file of signed messages, four lines each:
+//   msg: <message>
+//   s: <decimal>
+//   r: <decimal>
+//   m: <hex SHA-1 of the message>
+pub fn read_signed_messages(filename: &str) -> Result<Vec<SignedMessage>, Error> {
+    let lines = read_lines(filename)?;
+
+    lines
+        .chunks(4)
+        .map(|chunk| {
+            let field = |idx: usize, name: &str| {
+                chunk
+                    .get(idx)
+                    .and_then(|line| line.strip_prefix(name))
+                    .and_then(|line| line.strip_prefix(": "))
+                    .ok_or_else(|| format_err!("Expected a {} line in {:?}", name, chunk))
+            };
+            let number = |idx: usize, name: &str, radix: u32| {
+                let value = field(idx, name)?;
+                BigUint::parse_bytes(value.trim().as_bytes(), radix)
+                    .ok_or_else(|| format_err!("Invalid {}: {:?}", name, value))
+            };
+
+            let msg = field(0, "msg")?.to_string();
+            let hash = number(3, "m", 16)?;
+
+            if hash != message_hash(msg.as_bytes()) {
+                return Err(format_err!("Hash doesn't match message {:?}", msg));
+            }
+
+            Ok(SignedMessage {
+                msg,
+                hash,
+                signature: DsaSignature {
+                    r: number(2, "r", 10)?,
+                    s: number(1, "s", 10)?,
+                },
+            })
+        })
+        .collect()
+}
+
+// two signatures made with the same k share r, and then
+// k = (m1 - m2) / (s1 - s2) mod q
+pub fn x_from_repeated_nonce(
+    params: &DsaParams,
+    y: &BigUint,
+    signed_messages: &[SignedMessage],
+) -> Option<BigUint> {
+    let q = &params.q;
+
+    signed_messages
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| a.signature.r == b.signature.r)
+        .filter_map(|(a, b)| {
+            let hash_diff = (&a.hash % q + q - &b.hash % q) % q;
+            let s_diff = (&a.signature.s + q - &b.signature.s) % q;
+
+            let k = hash_diff * invmod(&s_diff, q)? % q;
+            x_from_nonce(params, &a.hash, &a.signature, &k)
+        })
+        .find(|x| modexp(&params.g, x, &params.p) == *y)
+}
+
+// with g = p + 1 (so g = 1 mod p), r = (y^z mod p) mod q and
+// s = r / z mod q verifies for any message, and any z
+pub fn magic_signature(params: &DsaParams, y: &BigUint, z: &BigUint) -> Option<DsaSignature> {
+    let r = modexp(y, z, &params.p) % &params.q;
+    let s = &r * invmod(z, &params.q)? % &params.q;
+
+    Some(DsaSignature { r, s })
+}
@@ -0,0 +1,135 @@
+use crate::cipher::aes::{cbc_encrypt, encrypt_block, pkcs7_pad, AES_BLOCK_SIZE};
+use crate::hash::MerkleDamgard;
+use std::iter;
+
+// given a valid secret-prefix MAC for `msg`, forge a MAC for
+// msg || glue_padding || extension, without knowing the key.
+//
+// The glue padding depends on the (unknown) key length, so each key
+// length up to `max_key_len` is tried against the `verify` oracle
+// (which takes a message and a MAC). Returns the forged message and MAC
+pub fn forge_secret_prefix_mac<H: MerkleDamgard>(
+    msg: &[u8],
+    mac: &[u8],
+    extension: &[u8],
+    max_key_len: usize,
+    verify: impl Fn(&[u8], &[u8]) -> bool,
+) -> Option<(Vec<u8>, Vec<u8>)> {
+    for key_len in 0..=max_key_len {
+        let prefix_len = (key_len + msg.len()) as u64;
+        let glue_padding = H::padding(prefix_len);
+
+        let mut forged_msg = msg.to_vec();
+        forged_msg.extend(&glue_padding);
+        forged_msg.extend(extension);
+
+        let mut hasher = H::from_digest(mac, prefix_len + glue_padding.len() as u64);
+        hasher.update(extension);
+        let forged_mac = hasher.digest();
+
+        if verify(&forged_msg, &forged_mac) {
+            return Some((forged_msg, forged_mac));
+        }
+    }
+
+    None
+}
+
+// given msg's CBC-MAC under an attacker-controlled IV, find the IV
+// under which `forged_msg` has the same MAC. Only the first block of
+// the message can be changed this way
+pub fn forge_cbc_mac_iv(msg: &[u8], iv: &[u8], forged_msg: &[u8]) -> Option<Vec<u8>> {
+    if msg.len() != forged_msg.len()
+        || msg.get(AES_BLOCK_SIZE..) != forged_msg.get(AES_BLOCK_SIZE..)
+    {
+        return None;
+    }
+
+    let mut forged_iv = iv.to_vec();
+    for (i, (m, f)) in msg.iter().zip(forged_msg).take(AES_BLOCK_SIZE).enumerate() {
+        forged_iv[i] ^= m ^ f;
+    }
+
+    Some(forged_iv)
+}
+
+// given fixed-IV CBC-MACs for `msg` and `extension`, build the message
+//   msg || padding || (extension's first block ^ msg's MAC) || rest of extension
+// whose MAC is extension's MAC: the MAC of the first part takes the
+// place of the zero IV when the chaining reaches the extension
+pub fn splice_cbc_mac(msg: &[u8], mac: &[u8], extension: &[u8]) -> Vec<u8> {
+    let mut forged_msg = pkcs7_pad(msg, AES_BLOCK_SIZE);
+
+    forged_msg.extend(
+        extension
+            .iter()
+            .zip(mac.iter().chain(iter::repeat(&0)))
+            .map(|(e, m)| e ^ m),
+    );
+
+    forged_msg
+}
+
+// CBC-MAC makes a poor hash when the key is known. Forge a message
+// starting with `prefix` that has the same CBC-MAC (under `key` and
+// `iv`) as `msg`:
+//   prefix || filler || glue || msg without its first block
+// where the filler pads the prefix out to a block boundary with spaces,
+// then adds one more block holding a counter, and the glue block steers
+// the CBC state to where msg's first block would have left it.
+//
+// The CBC state after the prefix is fixed, so each attempt only has to
+// encrypt the counter block. Counters are tried, up to `max_attempts`,
+// until `is_acceptable_glue` is happy with the glue block. The glue is
+// effectively 16 random bytes, so insisting that it's printable ASCII
+// takes around 2^23 attempts
+pub fn forge_cbc_mac_collision(
+    key: &[u8],
+    iv: &[u8],
+    msg: &[u8],
+    prefix: &[u8],
+    is_acceptable_glue: impl Fn(&[u8]) -> bool,
+    max_attempts: usize,
+) -> Option<Vec<u8>> {
+    if msg.len() < AES_BLOCK_SIZE {
+        return None;
+    }
+
+    let mut forged_msg = prefix.to_vec();
+    let padding_len = (AES_BLOCK_SIZE - prefix.len() % AES_BLOCK_SIZE) % AES_BLOCK_SIZE;
+    forged_msg.extend(vec![b' '; padding_len]);
+
+    // the CBC state after the (padded) prefix, before the counter block
+    let prefix_state = if forged_msg.is_empty() {
+        iv.to_vec()
+    } else {
+        let cyphertext = cbc_encrypt(key, iv, &forged_msg).ok()?;
+        cyphertext[forged_msg.len() - AES_BLOCK_SIZE..forged_msg.len()].to_vec()
+    };
+
+    for attempt in 0..max_attempts {
+        let counter = format!("{:>1$}", attempt, AES_BLOCK_SIZE).into_bytes();
+        let input: Vec<u8> = prefix_state
+            .iter()
+            .zip(&counter)
+            .map(|(s, c)| s ^ c)
+            .collect();
+        let state = encrypt_block(key, &input).ok()?;
+
+        let glue: Vec<u8> = state
+            .iter()
+            .zip(iv)
+            .zip(&msg[..AES_BLOCK_SIZE])
+            .map(|((s, i), m)| s ^ i ^ m)
+            .collect();
+
+        if is_acceptable_glue(&glue) {
+            forged_msg.extend(counter);
+            forged_msg.extend(glue);
+            forged_msg.extend(&msg[AES_BLOCK_SIZE..]);
+            return Some(forged_msg);
+        }
+    }
+
+    None
+}
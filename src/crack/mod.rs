@@ -1,3 +1,14 @@
 pub mod aes;
+pub mod compression;
+pub mod dh;
+pub mod dsa;
+pub mod mac;
+pub mod mt19937;
+pub mod multicollision;
+pub mod rsa;
+pub mod srp;
+pub mod timing;
+pub mod transfer;
 pub mod xor;
 pub mod user_profile;
+pub mod user_data;
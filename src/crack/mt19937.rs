@@ -0,0 +1,114 @@
+use crate::cipher::mt19937::{mt19937_cipher, MT19937, N};
+use crate::clock::Clock;
+use failure::{format_err, Error};
+use rand::Rng;
+
+// undo a `y ^= y >> shift` step. Each pass recovers another `shift`
+// bits, starting from the (untouched) most significant bits
+fn undo_right_shift_xor(y: u32, shift: u32) -> u32 {
+    let mut result = y;
+    for _ in 0..(32 / shift) {
+        result = y ^ (result >> shift);
+    }
+    result
+}
+
+// undo a `y ^= (y << shift) & mask` step, recovering
+// `shift` bits at a time from the least significant end
+fn undo_left_shift_xor_and(y: u32, shift: u32, mask: u32) -> u32 {
+    let mut result = y;
+    for _ in 0..(32 / shift) {
+        result = y ^ ((result << shift) & mask);
+    }
+    result
+}
+
+pub fn untemper(y: u32) -> u32 {
+    let mut y = undo_right_shift_xor(y, 18);
+    y = undo_left_shift_xor_and(y, 15, 0xefc6_0000);
+    y = undo_left_shift_xor_and(y, 7, 0x9d2c_5680);
+    undo_right_shift_xor(y, 11)
+}
+
+// given at least 624 consecutive outputs of a generator, rebuild its
+// internal state from the first 624 and splice it into a new generator,
+// which is then stepped past the rest of the outputs (checking that it
+// agrees with them), so it produces the same outputs as the original
+// from there on
+pub fn clone_mt19937(outputs: &[u32]) -> Result<MT19937, Error> {
+    if outputs.len() < N {
+        return Err(format_err!(
+            "Need {} consecutive outputs to clone MT19937, only got {}",
+            N,
+            outputs.len()
+        ));
+    }
+
+    let mut state = [0u32; N];
+    for (word, &output) in state.iter_mut().zip(&outputs[..N]) {
+        *word = untemper(output);
+    }
+
+    let mut mt = MT19937::from_state(state);
+    for (i, &output) in outputs.iter().enumerate().skip(N) {
+        if mt.next_u32() != output {
+            return Err(format_err!(
+                "Output {} doesn't follow on from the ones before it",
+                i
+            ));
+        }
+    }
+
+    Ok(mt)
+}
+
+// brute-force the 16-bit seed of an mt19937_cipher cyphertext,
+// given some plaintext known to sit at the end of the message
+pub fn recover_mt19937_cipher_seed(cyphertext: &[u8], known_suffix: &[u8]) -> Option<u16> {
+    if known_suffix.len() > cyphertext.len() {
+        return None;
+    }
+
+    let suffix_idx = cyphertext.len() - known_suffix.len();
+
+    (0..=u16::MAX).find(|&seed| {
+        let plaintext = mt19937_cipher(seed, cyphertext);
+        &plaintext[suffix_idx..] == known_suffix
+    })
+}
+
+// a 16 byte "password reset token", made from the
+// first few outputs of an MT19937 seeded with `seed`
+pub fn password_reset_token(seed: u32) -> Vec<u8> {
+    let mut mt = MT19937::seed(seed);
+
+    (0..4).flat_map(|_| mt.next_u32().to_le_bytes().to_vec()).collect()
+}
+
+// check whether a token could have come from password_reset_token, seeded
+// with a UNIX timestamp within `window` seconds before the clock's current time
+pub fn is_time_seeded_token(token: &[u8], clock: &impl Clock, window: u32) -> bool {
+    let now = clock.now();
+    (now.saturating_sub(window)..=now).any(|seed| password_reset_token(seed) == token)
+}
+
+// waits a random amount of time, seeds an MT19937 with the current
+// timestamp, waits some more, and hands back the generator's first output
+pub fn timestamp_seeded_oracle(clock: &impl Clock, rng: &mut impl Rng) -> u32 {
+    clock.sleep(rng.gen_range(40, 1000));
+
+    let mut mt = MT19937::seed(clock.now());
+
+    clock.sleep(rng.gen_range(40, 1000));
+
+    mt.next_u32()
+}
+
+// find the timestamp (within `window` seconds before the clock's
+// current time) that seeded a generator whose first output is `output`
+pub fn recover_timestamp_seed(output: u32, clock: &impl Clock, window: u32) -> Option<u32> {
+    let now = clock.now();
+    (now.saturating_sub(window)..=now)
+        .rev()
+        .find(|&seed| MT19937::seed(seed).next_u32() == output)
+}
@@ -0,0 +1,169 @@
+use crate::hash::CompressionFunction;
+use bit_vec::BitVec;
+use std::collections::HashMap;
+
+// a birthday search for two different blocks that take `state` to the
+// same new state, which costs about 2^(b/2) compressions for a b bit
+// state. Returns the blocks and the state they collide in
+pub fn find_block_collision(
+    hash: &impl CompressionFunction,
+    state: &[u8],
+) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    let mut seen: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+
+    // blocks with a counter at the start, and zeros after
+    for i in 0u128.. {
+        let mut block = vec![0; hash.block_size()];
+        for (b, c) in block.iter_mut().zip(&i.to_le_bytes()) {
+            *b = *c;
+        }
+        let new_state = hash.compress(state, &block);
+
+        if let Some(other_block) = seen.insert(new_state.clone(), block.clone()) {
+            return (other_block, block, new_state);
+        }
+    }
+
+    unreachable!()
+}
+
+// Joux's multicollision: n successive block collisions, so that every
+// one of the 2^n messages made by picking one block from each pair
+// hashes to the same state, for only n times the cost of one collision
+pub struct MultiCollision {
+    pub block_pairs: Vec<(Vec<u8>, Vec<u8>)>,
+    pub state: Vec<u8>,
+}
+
+impl MultiCollision {
+    pub fn new(hash: &impl CompressionFunction, n: usize) -> MultiCollision {
+        let mut multicollision = MultiCollision {
+            block_pairs: Vec::new(),
+            state: hash.initial_state(),
+        };
+        multicollision.extend(hash, n);
+        multicollision
+    }
+
+    // n more collisions, doubling the number of messages each time
+    pub fn extend(&mut self, hash: &impl CompressionFunction, n: usize) {
+        for _ in 0..n {
+            let (block_a, block_b, state) = find_block_collision(hash, &self.state);
+            self.block_pairs.push((block_a, block_b));
+            self.state = state;
+        }
+    }
+
+    // the message made by picking the second block of the i-th pair
+    // wherever choice i is set
+    pub fn message(&self, choices: &BitVec) -> Vec<u8> {
+        assert_eq!(choices.len(), self.block_pairs.len());
+
+        self.block_pairs
+            .iter()
+            .zip(choices.iter())
+            .flat_map(|((block_a, block_b), choice)| {
+                if choice {
+                    block_b.clone()
+                } else {
+                    block_a.clone()
+                }
+            })
+            .collect()
+    }
+
+    // every one of the 2^n messages, which had better not be too many
+    pub fn messages(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        let n = self.block_pairs.len();
+        assert!(n < 64, "Too many messages to list: 2^{}", n);
+
+        (0..1u64 << n).map(move |i| {
+            let choices = (0..n).map(|bit| i >> bit & 1 == 1).collect();
+            self.message(&choices)
+        })
+    }
+}
+
+// find two messages colliding in the cascaded hash f(x) || g(x), where
+// f is the cheaper (smaller state) hash. This costs little more than a
+// collision in g alone: a multicollision in f big enough for the
+// birthday bound in g (about 2^(b/2) messages for a b bit g) is likely
+// to contain a collision in g, and if not, it's doubled and searched
+// again
+pub fn cascade_collision(
+    f: &impl CompressionFunction,
+    g: &impl CompressionFunction,
+) -> (Vec<u8>, Vec<u8>) {
+    // enough messages for the birthday bound in g
+    let g_bits = g.initial_state().len() * 8;
+    let mut multicollision = MultiCollision::new(f, g_bits / 2);
+
+    loop {
+        let mut seen: HashMap<Vec<u8>, BitVec> = HashMap::new();
+        let mut found = None;
+
+        // walk the tree of choices, so that messages sharing a prefix
+        // share the work of hashing it under g
+        walk_choices(
+            g,
+            &multicollision.block_pairs,
+            0,
+            g.initial_state(),
+            &mut BitVec::new(),
+            &mut |choices, state| {
+                if let Some(other_choices) = seen.get(&state) {
+                    found = Some((other_choices.clone(), choices.clone()));
+                    return false;
+                }
+                seen.insert(state, choices.clone());
+                true
+            },
+        );
+
+        if let Some((choices_a, choices_b)) = found {
+            // the messages are the same length, so get the same padding
+            return (
+                multicollision.message(&choices_a),
+                multicollision.message(&choices_b),
+            );
+        }
+
+        multicollision.extend(f, 1);
+    }
+}
+
+// calls `visit` with the choices and final g state of each message in
+// the multicollision (from the i-th block pair on), stopping early if it
+// returns false
+fn walk_choices(
+    g: &impl CompressionFunction,
+    block_pairs: &[(Vec<u8>, Vec<u8>)],
+    i: usize,
+    state: Vec<u8>,
+    choices: &mut BitVec,
+    visit: &mut dyn FnMut(&BitVec, Vec<u8>) -> bool,
+) -> bool {
+    let (block_a, block_b) = match block_pairs.get(i) {
+        None => return visit(choices, state),
+        Some(pair) => pair,
+    };
+
+    for (choice, block) in [(false, block_a), (true, block_b)].iter() {
+        choices.push(*choice);
+        let carry_on = walk_choices(
+            g,
+            block_pairs,
+            i + 1,
+            g.compress(&state, block),
+            choices,
+            visit,
+        );
+        choices.pop();
+
+        if !carry_on {
+            return false;
+        }
+    }
+
+    true
+}
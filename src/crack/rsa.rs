@@ -0,0 +1,262 @@
+use crate::hash::sha1::sha1;
+use crate::pubkey::math::{crt, invmod, nth_root};
+use crate::pubkey::pkcs1::SHA1_DIGEST_INFO;
+use crate::pubkey::rsa::{to_bytes_padded, RsaPublicKey};
+use crate::rsa_oracle::{DecryptionOracle, PaddingOracle, ParityOracle};
+use failure::{format_err, Error};
+use num_bigint::BigUint;
+use num_traits::{CheckedSub, One, ToPrimitive, Zero};
+use std::cell::Cell;
+
+// Håstad's broadcast attack: given the same message encrypted under e
+// different public keys, all with exponent e, CRT gives m^e mod n_1*...*n_e.
+// As m < n_i for every i, m^e is smaller than that product, so it's
+// just m^e, and m is its plain integer e'th root
+pub fn hastad_broadcast(cyphertexts: &[(BigUint, RsaPublicKey)]) -> Result<BigUint, Error> {
+    let e = cyphertexts
+        .first()
+        .ok_or_else(|| format_err!("No cyphertexts"))?
+        .1
+        .e
+        .to_u32()
+        .ok_or_else(|| format_err!("Public exponent is too big"))?;
+
+    if cyphertexts.iter().any(|(_, key)| key.e != BigUint::from(e)) {
+        return Err(format_err!("Public exponents differ"));
+    }
+    if cyphertexts.len() < e as usize {
+        return Err(format_err!(
+            "Need {} cyphertexts, only have {}",
+            e,
+            cyphertexts.len()
+        ));
+    }
+
+    let congruences: Vec<(BigUint, BigUint)> = cyphertexts
+        .iter()
+        .take(e as usize)
+        .map(|(c, key)| (c.clone(), key.n.clone()))
+        .collect();
+
+    let m_e = crt(&congruences).ok_or_else(|| format_err!("Moduli aren't coprime"))?;
+
+    Ok(nth_root(&m_e, e))
+}
+
+// recover the plaintext behind `cyphertext` from an oracle that won't
+// decrypt it again: blind it as c' = s^e * c, have the oracle decrypt
+// that to p' = s * p, and unblind. `s` must be invertible mod n
+pub fn recover_unpadded_message(
+    oracle: &dyn DecryptionOracle,
+    cyphertext: &BigUint,
+    s: &BigUint,
+) -> Result<BigUint, Error> {
+    let public = oracle.public_key();
+    let n = &public.n;
+
+    let s_inv = invmod(s, n).ok_or_else(|| format_err!("s isn't invertible mod n"))?;
+
+    let blinded = public.encrypt(s) * cyphertext % n;
+    let blinded_plaintext = oracle.decrypt(&blinded)?;
+
+    Ok(blinded_plaintext * s_inv % n)
+}
+
+// forge a PKCS#1 v1.5 signature for `msg` that a verifier which doesn't
+// check where the hash ends will accept, for a key with e = 3.
+//
+// Any number whose cube starts 00 01 ff 00 DigestInfo H(msg) will do, as
+// the rest of the block is ignored. With enough garbage bytes after the
+// hash, the cube root of that prefix followed by ff's, rounded down, is
+// such a number
+pub fn forge_pkcs1_signature(public: &RsaPublicKey, msg: &[u8]) -> Result<Vec<u8>, Error> {
+    if public.e != BigUint::from(3u32) {
+        return Err(format_err!("Public exponent must be 3"));
+    }
+
+    let mut prefix = vec![0x00, 0x01, 0xff, 0x00];
+    prefix.extend(&SHA1_DIGEST_INFO);
+    prefix.extend(sha1(msg));
+
+    let garbage_len = public
+        .size()
+        .checked_sub(prefix.len())
+        .ok_or_else(|| format_err!("Modulus is too small"))?;
+
+    let mut upper = prefix.clone();
+    upper.extend(vec![0xff; garbage_len]);
+    let mut lower = prefix;
+    lower.extend(vec![0x00; garbage_len]);
+
+    let root = nth_root(&BigUint::from_bytes_be(&upper), 3);
+    if root.pow(3) < BigUint::from_bytes_be(&lower) {
+        return Err(format_err!(
+            "Not enough room after the hash to forge a signature"
+        ));
+    }
+
+    Ok(to_bytes_padded(&root, public.size()))
+}
+
+// decrypt `cyphertext` one bit at a time with a parity oracle.
+//
+// Multiplying the cyphertext by 2^e doubles the plaintext mod n. n is
+// odd, so 2p mod n is even exactly when 2p didn't wrap around n, i.e.
+// when p < n/2. Doubling again halves the remaining interval each time.
+// The bounds are kept as multiples of n / 2^i to avoid rounding errors.
+//
+// `progress`, if given, is handed the upper bound after every step
+pub fn decrypt_with_parity_oracle(
+    oracle: &dyn ParityOracle,
+    cyphertext: &BigUint,
+    mut progress: Option<&mut dyn FnMut(&BigUint)>,
+) -> BigUint {
+    let public = oracle.public_key();
+    let n = &public.n;
+    let multiplier = public.encrypt(&BigUint::from(2u32));
+
+    let mut lower = BigUint::zero();
+    let mut upper = BigUint::one();
+    let mut c = cyphertext.clone();
+
+    for i in 1..=n.bits() {
+        c = c * &multiplier % n;
+        lower <<= 1;
+        upper <<= 1;
+
+        if oracle.is_even(&c) {
+            upper -= 1u32;
+        } else {
+            lower += 1u32;
+        }
+
+        if let Some(progress) = progress.as_mut() {
+            progress(&((&upper * n) >> i));
+        }
+    }
+
+    (upper * n) >> n.bits()
+}
+
+#[derive(Clone, Debug)]
+pub struct BleichenbacherResult {
+    // the whole decrypted block, padding and all
+    pub block: Vec<u8>,
+    pub oracle_queries: usize,
+}
+
+fn ceil_div(a: &BigUint, b: &BigUint) -> BigUint {
+    (a + b - 1u32) / b
+}
+
+// Bleichenbacher's 1998 attack: decrypt a PKCS#1 v1.5 conforming
+// `cyphertext` with an oracle that only says whether a cyphertext's
+// plaintext starts 00 02.
+//
+// With B = 2^(8(k - 2)), a conforming plaintext lies in [2B, 3B). Finding
+// an s for which m*s is conforming too narrows down where m can be, and
+// repeating that with ever bigger s closes the range in on m
+pub fn bleichenbacher(
+    oracle: &dyn PaddingOracle,
+    cyphertext: &BigUint,
+) -> Result<BleichenbacherResult, Error> {
+    let public = oracle.public_key();
+    let n = &public.n;
+    let k = public.size();
+
+    let oracle_queries = Cell::new(0);
+    let is_conforming = |s: &BigUint| {
+        oracle_queries.set(oracle_queries.get() + 1);
+        oracle.is_conforming(&(cyphertext * public.encrypt(s) % n))
+    };
+
+    let b = BigUint::one() << (8 * (k - 2));
+    let two_b = &b * 2u32;
+    let three_b = &b * 3u32;
+
+    // step 1: the cyphertext is conforming already, so s_0 = 1
+    let mut intervals = vec![(two_b.clone(), &three_b - 1u32)];
+    let mut s = ceil_div(n, &three_b);
+
+    for i in 1.. {
+        s = if i == 1 || intervals.len() > 1 {
+            // step 2a/2b: search upwards from n / 3B, or from the last s
+            if i > 1 {
+                s += 1u32;
+            }
+            while !is_conforming(&s) {
+                s += 1u32;
+            }
+            s
+        } else {
+            // step 2c: a single interval [a, b] left, so search with
+            // r >= 2(b * s - 2B) / n, s in [(2B + rn) / b, (3B + rn) / a)
+            let (lower, upper) = &intervals[0];
+            let mut r = ceil_div(&(2u32 * (upper * &s - &two_b)), n);
+
+            'search: loop {
+                let mut new_s = ceil_div(&(&two_b + &r * n), upper);
+                let s_max = ceil_div(&(&three_b + &r * n), lower);
+
+                while new_s < s_max {
+                    if is_conforming(&new_s) {
+                        break 'search new_s;
+                    }
+                    new_s += 1u32;
+                }
+                r += 1u32;
+            }
+        };
+
+        // step 3: narrow every interval down to the m for which m * s
+        // lands in [2B, 3B) after wrapping around n some r times
+        let mut narrowed: Vec<(BigUint, BigUint)> = Vec::new();
+        for (lower, upper) in &intervals {
+            let r_min = ceil_div(
+                &(lower * &s + 1u32)
+                    .checked_sub(&three_b)
+                    .unwrap_or_default(),
+                n,
+            );
+            let r_max = (upper * &s).checked_sub(&two_b).unwrap_or_default() / n;
+
+            let mut r = r_min;
+            while r <= r_max {
+                let new_lower = ceil_div(&(&two_b + &r * n), &s).max(lower.clone());
+                let new_upper = ((&three_b - 1u32 + &r * n) / &s).min(upper.clone());
+
+                if new_lower <= new_upper {
+                    narrowed.push((new_lower, new_upper));
+                }
+                r += 1u32;
+            }
+        }
+
+        // merge overlapping intervals
+        narrowed.sort();
+        intervals = Vec::new();
+        for (lower, upper) in narrowed {
+            match intervals.last_mut() {
+                Some((_, last_upper)) if lower <= *last_upper => {
+                    *last_upper = upper.max(last_upper.clone());
+                }
+                _ => intervals.push((lower, upper)),
+            }
+        }
+
+        // step 4: done once there's a single candidate left
+        match intervals.as_slice() {
+            [] => return Err(format_err!("Ran out of candidate plaintexts")),
+            [(lower, upper)] if lower == upper => {
+                let block = to_bytes_padded(lower, k);
+                return Ok(BleichenbacherResult {
+                    block,
+                    oracle_queries: oracle_queries.get(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    unreachable!()
+}
@@ -0,0 +1,128 @@
+use crate::protocol::simple_srp;
+use crate::protocol::srp::{private_key, proof, session_key, Message};
+use crate::protocol::transport::Endpoint;
+use crate::protocol::unexpected;
+use crate::pubkey::dh::DhGroup;
+use crate::pubkey::math::modexp;
+use failure::Error;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+// log in as `email` without knowing the password, by sending a public
+// key that's a multiple of N (0, N, 2N, ...). The server computes
+// S = (A * v^u)^b mod N = 0, a session key the client also knows
+pub fn zero_key_login(
+    conn: Endpoint<Message>,
+    email: &str,
+    forged_public: BigUint,
+) -> Result<bool, Error> {
+    conn.send(Message::Hello {
+        email: email.to_string(),
+        public: forged_public,
+    })?;
+
+    let salt = match conn.recv()? {
+        Message::Challenge { salt, .. } => salt,
+        msg => return Err(unexpected(msg)),
+    };
+
+    conn.send(Message::Proof(proof(&session_key(&BigUint::zero()), &salt)))?;
+
+    match conn.recv()? {
+        Message::LoginResult(ok) => Ok(ok),
+        msg => Err(unexpected(msg)),
+    }
+}
+
+// what a fake simplified-SRP server learns from a client's login attempt
+#[derive(Clone, Debug)]
+pub struct CapturedLogin {
+    pub email: String,
+    pub client_public: BigUint,
+    pub salt: Vec<u8>,
+    pub proof: Vec<u8>,
+}
+
+// pose as a simplified-SRP server, handing the client b = 1 (so B = g)
+// and u = 1. The client's session secret is then
+// S = B^(a + ux) = A * g^x, which only depends on the password through x
+pub fn mitm_simple_srp(
+    conn: Endpoint<simple_srp::Message>,
+    group: &DhGroup,
+) -> Result<CapturedLogin, Error> {
+    let (email, client_public) = match conn.recv()? {
+        simple_srp::Message::Hello { email, public } => (email, public),
+        msg => return Err(unexpected(msg)),
+    };
+
+    let salt = Vec::new();
+
+    conn.send(simple_srp::Message::Challenge {
+        salt: salt.clone(),
+        public: group.g.clone(),
+        u: BigUint::one(),
+    })?;
+
+    let proof = match conn.recv()? {
+        simple_srp::Message::Proof(proof) => proof,
+        msg => return Err(unexpected(msg)),
+    };
+
+    conn.send(simple_srp::Message::LoginResult(false))?;
+
+    Ok(CapturedLogin {
+        email,
+        client_public,
+        salt,
+        proof,
+    })
+}
+
+// try every word in `dictionary` as the password behind a login captured
+// by mitm_simple_srp, split across all available cores
+pub fn crack_simple_srp_password(
+    group: &DhGroup,
+    captured: &CapturedLogin,
+    dictionary: &[String],
+) -> Option<String> {
+    let num_threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = dictionary.len().div_ceil(num_threads).max(1);
+    let found = AtomicBool::new(false);
+
+    let matches_password = |password: &str| {
+        let x = private_key(&captured.salt, &captured.email, password);
+        let secret = &captured.client_public * modexp(&group.g, &x, &group.p) % &group.p;
+
+        proof(&session_key(&secret), &captured.salt) == captured.proof
+    };
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = dictionary
+            .chunks(chunk_size)
+            .map(|words| {
+                let found = &found;
+                let matches_password = &matches_password;
+
+                scope.spawn(move || {
+                    for word in words {
+                        if found.load(Ordering::Relaxed) {
+                            return None;
+                        }
+                        if matches_password(word) {
+                            found.store(true, Ordering::Relaxed);
+                            return Some(word.clone());
+                        }
+                    }
+                    None
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok().flatten())
+            .next()
+    })
+}
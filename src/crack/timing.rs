@@ -0,0 +1,125 @@
+use failure::{format_err, Error};
+use std::cmp::Reverse;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+// far longer than any signature check should take, but a server that's
+// stopped answering shouldn't hang us forever
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ask the server at `addr` whether `signature` is valid for `file`,
+// returning its answer and how long it took to give it
+pub fn time_signature_request(
+    addr: SocketAddr,
+    file: &str,
+    signature: &[u8],
+) -> Result<(bool, Duration), Error> {
+    let start = Instant::now();
+
+    let mut stream = TcpStream::connect(addr)?;
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+    write!(
+        stream,
+        "GET /test?file={}&signature={} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        file,
+        hex::encode(signature),
+        addr
+    )?;
+
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+
+    let elapsed = start.elapsed();
+
+    match status_line.split_whitespace().nth(1) {
+        Some("200") => Ok((true, elapsed)),
+        Some(_) => Ok((false, elapsed)),
+        None => Err(format_err!("Malformed response: {:?}", status_line)),
+    }
+}
+
+// a low quantile, rather than the median: noise on a request's timing
+// (scheduling, oversleeping) can only ever make it slower
+fn low_quantile(timings: &[Duration]) -> Duration {
+    let mut sorted = timings.to_vec();
+    sorted.sort();
+    sorted[sorted.len() / 10]
+}
+
+// recover a valid `signature_len` byte signature for `file` from a server
+// whose signature check takes longer the more leading bytes are correct.
+//
+// A single timing is far too noisy to pick out the right byte, so every
+// candidate is timed `samples` times, and the slowest few are then timed
+// again, `4 * samples` times each.
+//
+// How much slower the right first byte is than the rest tells us how long
+// one matching byte takes. If a later position has no candidate that stands
+// out by at least half that much, the byte before it must have been wrong,
+// so we back up and pick that one again
+pub fn crack_signature_timing(
+    addr: SocketAddr,
+    file: &str,
+    signature_len: usize,
+    samples: usize,
+) -> Result<Vec<u8>, Error> {
+    let max_backtracks = 8 * signature_len;
+
+    let mut signature = vec![0u8; signature_len];
+    let mut step = Duration::from_secs(0);
+    let mut backtracks = 0;
+    let mut idx = 0;
+
+    while idx < signature_len {
+        let mut timings: Vec<Vec<Duration>> = vec![Vec::new(); 256];
+        let mut candidates: Vec<u8> = (0..=255).collect();
+
+        for &(num_samples, keep) in &[(samples, 8), (4 * samples, 1)] {
+            for _ in 0..num_samples {
+                for &byte in &candidates {
+                    signature[idx] = byte;
+                    let (is_valid, elapsed) = time_signature_request(addr, file, &signature)?;
+                    if is_valid {
+                        return Ok(signature);
+                    }
+                    timings[byte as usize].push(elapsed);
+                }
+            }
+
+            candidates.sort_by_key(|&b| Reverse(low_quantile(&timings[b as usize])));
+            candidates.truncate(keep);
+        }
+
+        let all_timings: Vec<Duration> = timings.iter().flatten().cloned().collect();
+        let winner = candidates[0];
+        let winner_step =
+            low_quantile(&timings[winner as usize]).saturating_sub(low_quantile(&all_timings));
+
+        if idx == 0 {
+            step = winner_step;
+        } else if winner_step < step / 2 || idx == signature_len - 1 {
+            // with the right prefix, one of the candidates for
+            // the last byte would have been accepted outright
+            backtracks += 1;
+            if backtracks > max_backtracks {
+                return Err(format_err!(
+                    "Gave up after backtracking {} times",
+                    max_backtracks
+                ));
+            }
+            idx -= 1;
+            continue;
+        }
+
+        signature[idx] = winner;
+        idx += 1;
+    }
+
+    Err(format_err!(
+        "Cracked all {} bytes, but the server didn't accept the signature {}",
+        signature_len,
+        hex::encode(&signature)
+    ))
+}
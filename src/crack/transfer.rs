@@ -0,0 +1,59 @@
+use crate::cipher::aes::AES_BLOCK_SIZE;
+use crate::crack::mac::{forge_cbc_mac_iv, splice_cbc_mac};
+use crate::transfer::Transfer;
+
+// rewrite a transfer request (message || IV || MAC) signed for the
+// attacker's own account so that it moves money out of `victim`'s
+// instead. The victim's account number has to be the same length as
+// the attacker's, so that only the first block changes
+pub fn forge_transfer_from(request: &[u8], victim: u32) -> Option<Vec<u8>> {
+    if request.len() < 2 * AES_BLOCK_SIZE {
+        return None;
+    }
+    let (msg, iv_and_mac) = request.split_at(request.len() - 2 * AES_BLOCK_SIZE);
+    let (iv, mac) = iv_and_mac.split_at(AES_BLOCK_SIZE);
+
+    let transfer = String::from_utf8_lossy(msg).parse::<Transfer>().ok()?;
+    let forged_msg = Transfer {
+        from: victim,
+        ..transfer
+    }
+    .to_string()
+    .into_bytes();
+
+    let forged_iv = forge_cbc_mac_iv(msg, iv, &forged_msg)?;
+
+    let mut forged_request = forged_msg;
+    forged_request.extend(forged_iv);
+    forged_request.extend(mac);
+    Some(forged_request)
+}
+
+// given a captured multi-transfer request (message || MAC) from the
+// victim's account, and one the attacker signed for their own account,
+// splice the attacker's transactions onto the end of the victim's.
+//
+// The attacker's first block gets scrambled in the process, and runs
+// into (and spoils) the victim's last transaction, which the server
+// then skips. Their account number should be short enough that
+// "from=...&tx_list=" fits in that block, and they'll want to pad it out
+// with a throwaway transaction. Fails if the scrambled block happens to
+// contain a `&`, which would cut the victim's tx_list short
+pub fn forge_multi_transfer(victim_request: &[u8], attacker_request: &[u8]) -> Option<Vec<u8>> {
+    if victim_request.len() < AES_BLOCK_SIZE || attacker_request.len() < AES_BLOCK_SIZE {
+        return None;
+    }
+    let (victim_msg, victim_mac) = victim_request.split_at(victim_request.len() - AES_BLOCK_SIZE);
+    let (attacker_msg, attacker_mac) =
+        attacker_request.split_at(attacker_request.len() - AES_BLOCK_SIZE);
+
+    let forged_msg = splice_cbc_mac(victim_msg, victim_mac, attacker_msg);
+
+    if forged_msg[forged_msg.len() - attacker_msg.len()..].contains(&b'&') {
+        return None;
+    }
+
+    let mut forged_request = forged_msg;
+    forged_request.extend(attacker_mac);
+    Some(forged_request)
+}
@@ -0,0 +1,86 @@
+use crate::cipher::aes::AES_BLOCK_SIZE;
+use crate::user_data::UserDataOracle;
+use failure::{format_err, Error};
+
+fn first_difference(a: &[u8], b: &[u8]) -> Option<usize> {
+    a.iter().zip(b).position(|(x, y)| x != y)
+}
+
+// forge a cyphertext that the oracle decrypts to a user containing
+// ";admin=true;", by bitflipping an encrypted userdata value.
+//
+// Works against both CBC (where flipping a cyphertext bit flips the
+// same bit one block later in the plaintext, scrambling the block
+// we flipped in) and CTR (where it flips the very same plaintext bit)
+pub fn make_admin_user_data(oracle: &impl UserDataOracle) -> Result<Vec<u8>, Error> {
+    let target = b";admin=true;";
+
+    // disguise ';' and '=' (which the oracle escapes) as ':' and '<'
+    let disguised: Vec<u8> = target
+        .iter()
+        .map(|&b| if b == b';' || b == b'=' { b ^ 1 } else { b })
+        .collect();
+
+    let encrypted_a = oracle.encrypt_user_data("A")?;
+    let encrypted_b = oracle.encrypt_user_data("B")?;
+
+    let first_varying_idx = first_difference(&encrypted_a, &encrypted_b)
+        .ok_or_else(|| format_err!("Oracle output doesn't depend on userdata"))?;
+    let num_varying = encrypted_a
+        .iter()
+        .zip(&encrypted_b)
+        .filter(|(x, y)| x != y)
+        .count();
+
+    // a single changed byte only changes a single cyphertext byte
+    // in a stream cipher, whereas CBC scrambles everything after it
+    let is_stream_cipher = num_varying == 1;
+
+    let (userdata_idx, flip_distance) = if is_stream_cipher {
+        (first_varying_idx, 0)
+    } else {
+        // grow the input until the byte we change spills over into the
+        // next block, which tells us where in its block the userdata starts.
+        // (compare whole blocks, since two different cyphertext blocks
+        // can still happen to start with the same byte)
+        let first_varying_block = first_varying_idx / AES_BLOCK_SIZE;
+        let spill_len = (1..=AES_BLOCK_SIZE)
+            .find(|&len| {
+                let filler = "A".repeat(len);
+                let encrypted_a = oracle.encrypt_user_data(&format!("{}A", filler));
+                let encrypted_b = oracle.encrypt_user_data(&format!("{}B", filler));
+
+                match (encrypted_a, encrypted_b) {
+                    (Ok(a), Ok(b)) => first_difference(&a, &b)
+                        .is_some_and(|idx| idx / AES_BLOCK_SIZE != first_varying_block),
+                    _ => false,
+                }
+            })
+            .ok_or_else(|| format_err!("Could not find where the userdata starts"))?;
+
+        (
+            (first_varying_block + 1) * AES_BLOCK_SIZE - spill_len,
+            AES_BLOCK_SIZE,
+        )
+    };
+
+    // align the disguised target to a block boundary, with a full block of
+    // filler in front of it, which gets scrambled when bitflipping in CBC mode
+    let align_len = (AES_BLOCK_SIZE - userdata_idx % AES_BLOCK_SIZE) % AES_BLOCK_SIZE;
+    let filler_len = align_len + AES_BLOCK_SIZE;
+    let target_idx = userdata_idx + filler_len;
+
+    let attacker_str = format!(
+        "{}{}",
+        "A".repeat(filler_len),
+        String::from_utf8(disguised.clone())?
+    );
+
+    let mut cyphertext = oracle.encrypt_user_data(&attacker_str)?;
+
+    for (i, (d, t)) in disguised.iter().zip(target).enumerate() {
+        cyphertext[target_idx + i - flip_distance] ^= d ^ t;
+    }
+
+    Ok(cyphertext)
+}
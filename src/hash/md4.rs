@@ -0,0 +1,130 @@
+// MD4 as specified in RFC 1320, with the same exposed,
+// resumable state as our SHA-1 implementation
+use crate::hash::{BlockBuffer, MerkleDamgard};
+
+pub const BLOCK_SIZE: usize = 64;
+pub const DIGEST_SIZE: usize = 16;
+
+pub const INITIAL_STATE: [u32; 4] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+
+#[derive(Clone, Debug)]
+pub struct Md4 {
+    pub state: [u32; 4],
+    blocks: BlockBuffer,
+}
+
+// the MD4 compression function: mixes a single 64 byte block into the state
+pub fn compress(state: &mut [u32; 4], block: &[u8]) {
+    assert_eq!(block.len(), BLOCK_SIZE);
+
+    let mut x = [0u32; 16];
+    for (i, word) in block.chunks(4).enumerate() {
+        x[i] = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+    }
+
+    let f = |x: u32, y: u32, z: u32| (x & y) | (!x & z);
+    let g = |x: u32, y: u32, z: u32| (x & y) | (x & z) | (y & z);
+    let h = |x: u32, y: u32, z: u32| x ^ y ^ z;
+
+    // a = (a + round_fn(b, c, d) + x + k) <<< s
+    let step = |a: u32, round_fn_result: u32, x: u32, k: u32, s: u32| {
+        a.wrapping_add(round_fn_result)
+            .wrapping_add(x)
+            .wrapping_add(k)
+            .rotate_left(s)
+    };
+
+    let [mut a, mut b, mut c, mut d] = *state;
+
+    // round 1
+    for &i in &[0, 4, 8, 12] {
+        a = step(a, f(b, c, d), x[i], 0, 3);
+        d = step(d, f(a, b, c), x[i + 1], 0, 7);
+        c = step(c, f(d, a, b), x[i + 2], 0, 11);
+        b = step(b, f(c, d, a), x[i + 3], 0, 19);
+    }
+
+    // round 2
+    for &i in &[0, 1, 2, 3] {
+        a = step(a, g(b, c, d), x[i], 0x5a82_7999, 3);
+        d = step(d, g(a, b, c), x[i + 4], 0x5a82_7999, 5);
+        c = step(c, g(d, a, b), x[i + 8], 0x5a82_7999, 9);
+        b = step(b, g(c, d, a), x[i + 12], 0x5a82_7999, 13);
+    }
+
+    // round 3
+    for &i in &[0, 2, 1, 3] {
+        a = step(a, h(b, c, d), x[i], 0x6ed9_eba1, 3);
+        d = step(d, h(a, b, c), x[i + 8], 0x6ed9_eba1, 9);
+        c = step(c, h(d, a, b), x[i + 4], 0x6ed9_eba1, 11);
+        b = step(b, h(c, d, a), x[i + 12], 0x6ed9_eba1, 15);
+    }
+
+    for (s, v) in state.iter_mut().zip(&[a, b, c, d]) {
+        *s = s.wrapping_add(*v);
+    }
+}
+
+// same as SHA-1's padding, except the length is little-endian
+pub fn padding(message_len: u64) -> Vec<u8> {
+    let mut pad = vec![0x80];
+    let zeros = (BLOCK_SIZE + 55 - (message_len as usize % BLOCK_SIZE)) % BLOCK_SIZE;
+    pad.extend(vec![0; zeros]);
+    pad.extend(&(message_len * 8).to_le_bytes());
+    pad
+}
+
+pub fn state_from_digest(digest: &[u8]) -> [u32; 4] {
+    let mut state = [0u32; 4];
+    for (s, word) in state.iter_mut().zip(digest.chunks(4)) {
+        *s = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    state
+}
+
+impl Md4 {
+    // like from_digest, but from the state itself
+    pub fn from_state(state: [u32; 4], length: u64) -> Md4 {
+        Md4 {
+            state,
+            blocks: BlockBuffer::new(BLOCK_SIZE, length),
+        }
+    }
+}
+
+impl MerkleDamgard for Md4 {
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const DIGEST_SIZE: usize = DIGEST_SIZE;
+
+    fn new() -> Md4 {
+        Md4::from_state(INITIAL_STATE, 0)
+    }
+
+    fn from_digest(digest: &[u8], length: u64) -> Md4 {
+        Md4::from_state(state_from_digest(digest), length)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.blocks.update(data, |block| compress(state, block));
+    }
+
+    fn digest(mut self) -> Vec<u8> {
+        let pad = padding(self.blocks.length());
+        let state = &mut self.state;
+        self.blocks.finish(&pad, |block| compress(state, block));
+
+        self.state
+            .iter()
+            .flat_map(|s| s.to_le_bytes().to_vec())
+            .collect()
+    }
+
+    fn padding(message_len: u64) -> Vec<u8> {
+        padding(message_len)
+    }
+}
+
+pub fn md4(data: &[u8]) -> Vec<u8> {
+    Md4::hash(data)
+}
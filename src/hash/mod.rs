@@ -0,0 +1,92 @@
+pub mod md4;
+pub mod sha1;
+pub mod sha256;
+pub mod toy;
+
+// a hash built by iterating a compression function over fixed-size blocks
+// of a padded message (e.g. MD4, SHA-1). Hashers can be resumed from the
+// state encoded in a digest, which is what makes length extension possible
+pub trait MerkleDamgard: Sized {
+    const BLOCK_SIZE: usize;
+    const DIGEST_SIZE: usize;
+
+    fn new() -> Self;
+
+    // resume hashing from the state in `digest`, as if `length` bytes
+    // (which should be a whole number of blocks) had already been processed
+    fn from_digest(digest: &[u8], length: u64) -> Self;
+
+    fn update(&mut self, data: &[u8]);
+
+    fn digest(self) -> Vec<u8>;
+
+    // the padding appended to a message of `message_len` bytes
+    fn padding(message_len: u64) -> Vec<u8>;
+
+    fn hash(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.digest()
+    }
+}
+
+// just the compression function of an iterated hash, working on raw
+// byte states of whatever size. That's all that generic attacks on the
+// iteration itself (like multicollisions) need to know about a hash
+pub trait CompressionFunction {
+    fn block_size(&self) -> usize;
+
+    fn initial_state(&self) -> Vec<u8>;
+
+    // mix a single block into the state
+    fn compress(&self, state: &[u8], block: &[u8]) -> Vec<u8>;
+
+    // iterate the compression function over whole blocks, without padding
+    fn compress_blocks(&self, state: &[u8], blocks: &[u8]) -> Vec<u8> {
+        blocks
+            .chunks(self.block_size())
+            .fold(state.to_vec(), |state, block| self.compress(&state, block))
+    }
+}
+
+// the part of a MerkleDamgard hasher that's the same for every hash:
+// collecting input into whole blocks for the compression function, and
+// counting the message length for the padding
+#[derive(Clone, Debug)]
+struct BlockBuffer {
+    block_size: usize,
+    buffer: Vec<u8>,
+    length: u64,
+}
+
+impl BlockBuffer {
+    // as if `length` bytes (a whole number of blocks) had already been fed in
+    fn new(block_size: usize, length: u64) -> BlockBuffer {
+        BlockBuffer {
+            block_size,
+            buffer: Vec::with_capacity(block_size),
+            length,
+        }
+    }
+
+    fn length(&self) -> u64 {
+        self.length
+    }
+
+    fn update(&mut self, data: &[u8], mut compress: impl FnMut(&[u8])) {
+        self.length += data.len() as u64;
+        self.buffer.extend(data);
+
+        let num_blocks = self.buffer.len() / self.block_size;
+        for block in self.buffer.chunks(self.block_size).take(num_blocks) {
+            compress(block);
+        }
+        self.buffer.drain(..num_blocks * self.block_size);
+    }
+
+    // feed in the padding, which should leave nothing over
+    fn finish(mut self, padding: &[u8], compress: impl FnMut(&[u8])) {
+        self.update(padding, compress);
+        assert!(self.buffer.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+// SHA-1 as specified in FIPS 180-4. Its internal state is exposed, and
+// hashing can be resumed from an arbitrary state, to allow for
+// length-extension attacks on naively constructed MACs
+use crate::hash::{BlockBuffer, MerkleDamgard};
+
+pub const BLOCK_SIZE: usize = 64;
+pub const DIGEST_SIZE: usize = 20;
+
+pub const INITIAL_STATE: [u32; 5] = [
+    0x6745_2301,
+    0xefcd_ab89,
+    0x98ba_dcfe,
+    0x1032_5476,
+    0xc3d2_e1f0,
+];
+
+#[derive(Clone, Debug)]
+pub struct Sha1 {
+    pub state: [u32; 5],
+    blocks: BlockBuffer,
+}
+
+// the SHA-1 compression function: mixes a single 64 byte block into the state
+pub fn compress(state: &mut [u32; 5], block: &[u8]) {
+    assert_eq!(block.len(), BLOCK_SIZE);
+
+    let mut w = [0u32; 80];
+    for (i, word) in block.chunks(4).enumerate() {
+        w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+
+    for (i, &w_i) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+            20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+            _ => (b ^ c ^ d, 0xca62_c1d6),
+        };
+
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(w_i);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (s, v) in state.iter_mut().zip(&[a, b, c, d, e]) {
+        *s = s.wrapping_add(*v);
+    }
+}
+
+// the "glue" padding appended to a message of `message_len` bytes:
+// a 1 bit, zeros, and the message length in bits (big-endian)
+pub fn padding(message_len: u64) -> Vec<u8> {
+    let mut pad = vec![0x80];
+    let zeros = (BLOCK_SIZE + 55 - (message_len as usize % BLOCK_SIZE)) % BLOCK_SIZE;
+    pad.extend(vec![0; zeros]);
+    pad.extend(&(message_len * 8).to_be_bytes());
+    pad
+}
+
+pub fn state_from_digest(digest: &[u8]) -> [u32; 5] {
+    let mut state = [0u32; 5];
+    for (s, word) in state.iter_mut().zip(digest.chunks(4)) {
+        *s = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    state
+}
+
+impl Sha1 {
+    // like from_digest, but from the state itself
+    pub fn from_state(state: [u32; 5], length: u64) -> Sha1 {
+        Sha1 {
+            state,
+            blocks: BlockBuffer::new(BLOCK_SIZE, length),
+        }
+    }
+}
+
+impl MerkleDamgard for Sha1 {
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const DIGEST_SIZE: usize = DIGEST_SIZE;
+
+    fn new() -> Sha1 {
+        Sha1::from_state(INITIAL_STATE, 0)
+    }
+
+    fn from_digest(digest: &[u8], length: u64) -> Sha1 {
+        Sha1::from_state(state_from_digest(digest), length)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.blocks.update(data, |block| compress(state, block));
+    }
+
+    fn digest(mut self) -> Vec<u8> {
+        let pad = padding(self.blocks.length());
+        let state = &mut self.state;
+        self.blocks.finish(&pad, |block| compress(state, block));
+
+        self.state
+            .iter()
+            .flat_map(|s| s.to_be_bytes().to_vec())
+            .collect()
+    }
+
+    fn padding(message_len: u64) -> Vec<u8> {
+        padding(message_len)
+    }
+}
+
+pub fn sha1(data: &[u8]) -> Vec<u8> {
+    Sha1::hash(data)
+}
@@ -0,0 +1,195 @@
+// SHA-256 as specified in FIPS 180-4, in the same shape as our SHA-1
+use crate::hash::{BlockBuffer, MerkleDamgard};
+
+pub const BLOCK_SIZE: usize = 64;
+pub const DIGEST_SIZE: usize = 32;
+
+pub const INITIAL_STATE: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+const K: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+#[derive(Clone, Debug)]
+pub struct Sha256 {
+    pub state: [u32; 8],
+    blocks: BlockBuffer,
+}
+
+// the SHA-256 compression function: mixes a single 64 byte block into the state
+pub fn compress(state: &mut [u32; 8], block: &[u8]) {
+    assert_eq!(block.len(), BLOCK_SIZE);
+
+    let mut w = [0u32; 64];
+    for (i, word) in block.chunks(4).enumerate() {
+        w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    for (&k_i, &w_i) in K.iter().zip(w.iter()) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(k_i)
+            .wrapping_add(w_i);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (s, v) in state.iter_mut().zip(&[a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(*v);
+    }
+}
+
+// SHA-256 pads exactly like SHA-1
+pub fn padding(message_len: u64) -> Vec<u8> {
+    crate::hash::sha1::padding(message_len)
+}
+
+pub fn state_from_digest(digest: &[u8]) -> [u32; 8] {
+    let mut state = [0u32; 8];
+    for (s, word) in state.iter_mut().zip(digest.chunks(4)) {
+        *s = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    state
+}
+
+impl Sha256 {
+    // like from_digest, but from the state itself
+    pub fn from_state(state: [u32; 8], length: u64) -> Sha256 {
+        Sha256 {
+            state,
+            blocks: BlockBuffer::new(BLOCK_SIZE, length),
+        }
+    }
+}
+
+impl MerkleDamgard for Sha256 {
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const DIGEST_SIZE: usize = DIGEST_SIZE;
+
+    fn new() -> Sha256 {
+        Sha256::from_state(INITIAL_STATE, 0)
+    }
+
+    fn from_digest(digest: &[u8], length: u64) -> Sha256 {
+        Sha256::from_state(state_from_digest(digest), length)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.blocks.update(data, |block| compress(state, block));
+    }
+
+    fn digest(mut self) -> Vec<u8> {
+        let pad = padding(self.blocks.length());
+        let state = &mut self.state;
+        self.blocks.finish(&pad, |block| compress(state, block));
+
+        self.state
+            .iter()
+            .flat_map(|s| s.to_be_bytes().to_vec())
+            .collect()
+    }
+
+    fn padding(message_len: u64) -> Vec<u8> {
+        padding(message_len)
+    }
+}
+
+pub fn sha256(data: &[u8]) -> Vec<u8> {
+    Sha256::hash(data)
+}
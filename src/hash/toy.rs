@@ -0,0 +1,80 @@
+// a deliberately weak Merkle-Damgard hash, with a state of only a few
+// bytes, for demonstrating generic attacks on iterated hashes. The
+// compression function encrypts each message block under the (zero
+// padded) state with AES, keeping the first few bytes of the result.
+//
+// The state size is picked at runtime, so rather than MerkleDamgard,
+// which deals in fixed-size digests, this is a CompressionFunction
+use crate::cipher::aes::{encrypt_block, AES_BLOCK_SIZE};
+use crate::hash::CompressionFunction;
+use std::cell::Cell;
+
+pub const BLOCK_SIZE: usize = AES_BLOCK_SIZE;
+
+const INITIAL_STATE: [u8; AES_BLOCK_SIZE] = [
+    0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10,
+];
+
+pub struct ToyHash {
+    state_size: usize,
+    // how many times the compression function has been called, which is
+    // the cost of an attack
+    compressions: Cell<u64>,
+}
+
+impl ToyHash {
+    // a hash with a `state_size` byte state (and digest)
+    pub fn new(state_size: usize) -> ToyHash {
+        assert!(state_size > 0 && state_size <= AES_BLOCK_SIZE);
+
+        ToyHash {
+            state_size,
+            compressions: Cell::new(0),
+        }
+    }
+
+    pub fn state_size(&self) -> usize {
+        self.state_size
+    }
+
+    pub fn compressions(&self) -> u64 {
+        self.compressions.get()
+    }
+
+    pub fn hash(&self, msg: &[u8]) -> Vec<u8> {
+        let mut padded_msg = msg.to_vec();
+        padded_msg.extend(padding(msg.len() as u64));
+
+        self.compress_blocks(&self.initial_state(), &padded_msg)
+    }
+}
+
+impl CompressionFunction for ToyHash {
+    fn block_size(&self) -> usize {
+        BLOCK_SIZE
+    }
+
+    fn initial_state(&self) -> Vec<u8> {
+        INITIAL_STATE[..self.state_size].to_vec()
+    }
+
+    fn compress(&self, state: &[u8], block: &[u8]) -> Vec<u8> {
+        self.compressions.set(self.compressions.get() + 1);
+
+        let mut key = state.to_vec();
+        key.resize(AES_BLOCK_SIZE, 0);
+
+        let mut new_state = encrypt_block(&key, block).unwrap();
+        new_state.truncate(self.state_size);
+        new_state
+    }
+}
+
+// MD-strengthening, as for SHA-1, but with 16 byte blocks
+pub fn padding(message_len: u64) -> Vec<u8> {
+    let mut pad = vec![0x80];
+    let zeros = (BLOCK_SIZE + 7 - (message_len as usize % BLOCK_SIZE)) % BLOCK_SIZE;
+    pad.extend(vec![0; zeros]);
+    pad.extend(&(message_len * 8).to_be_bytes());
+    pad
+}
@@ -0,0 +1,170 @@
+use crate::hash::sha1::{self, Sha1};
+use crate::mac::hmac::hmac;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// byte-at-a-time comparison that exits early, and sleeps for
+// `delay` after every matching byte, leaking how many bytes matched
+pub fn insecure_compare(a: &[u8], b: &[u8], delay: Duration) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    for (x, y) in a.iter().zip(b) {
+        if x != y {
+            return false;
+        }
+        thread::sleep(delay);
+    }
+
+    true
+}
+
+// a tiny HTTP server, listening on 127.0.0.1, which answers
+// `GET /test?file=foo&signature=46b4ec58...` with a 200 if the signature
+// is the (hex-encoded) HMAC-SHA1 of the file name, truncated to `mac_len`
+// bytes, and a 500 otherwise. Signatures are checked with insecure_compare,
+// sleeping `delay` per byte.
+//
+// A handful of worker threads share the listener, so a slow
+// comparison doesn't hold up other clients
+pub struct HmacServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+const WORKERS: usize = 8;
+
+// a client that connects and never sends its request shouldn't tie up a
+// worker (or stop the server from shutting down) forever
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+impl HmacServer {
+    pub fn start(key: &[u8], mac_len: usize, delay: Duration) -> io::Result<HmacServer> {
+        if mac_len > sha1::DIGEST_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "MAC length is longer than an HMAC-SHA1",
+            ));
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let workers = (0..WORKERS)
+            .map(|_| {
+                let listener = listener.try_clone()?;
+                let key = key.to_vec();
+                let shutdown = shutdown.clone();
+
+                Ok(thread::spawn(move || {
+                    for stream in listener.incoming() {
+                        if shutdown.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        if let Ok(stream) = stream {
+                            // a misbehaving client shouldn't take the server down
+                            let _ = handle_connection(stream, &key, mac_len, delay);
+                        }
+                    }
+                }))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(HmacServer {
+            addr,
+            shutdown,
+            workers,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for HmacServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // wake each worker up from accept(), so it sees the shutdown flag
+        for _ in &self.workers {
+            let _ = TcpStream::connect(self.addr);
+        }
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn parse_query(target: &str) -> Option<(String, Vec<u8>)> {
+    let query = target.strip_prefix("/test?")?;
+
+    let terms = query
+        .split('&')
+        .map(|term| term.splitn(2, '=').collect())
+        .collect::<Vec<Vec<_>>>();
+
+    let file = terms
+        .iter()
+        .find(|t| t[0] == "file")
+        .and_then(|t| t.get(1))?
+        .to_string();
+    let signature = terms
+        .iter()
+        .find(|t| t[0] == "signature")
+        .and_then(|t| t.get(1))
+        .and_then(|val| hex::decode(val).ok())?;
+
+    Some((file, signature))
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    key: &[u8],
+    mac_len: usize,
+    delay: Duration,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // skip over the headers
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header == "\r\n" {
+            break;
+        }
+    }
+
+    let is_valid = match request_line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["GET", target, _] => parse_query(target)
+            .map(|(file, signature)| {
+                let mac = hmac::<Sha1>(key, file.as_bytes());
+                insecure_compare(&mac[..mac_len], &signature, delay)
+            })
+            .unwrap_or(false),
+        _ => false,
+    };
+
+    let status = if is_valid {
+        "200 OK"
+    } else {
+        "500 Internal Server Error"
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,21 @@
+use crate::cipher::aes::{cbc_encrypt, CipherError, AES_BLOCK_SIZE};
+
+// CBC-MAC: the last block of the (PKCS#7 padded) AES-CBC encryption of `msg`
+pub fn cbc_mac(key: &[u8], iv: &[u8], msg: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cyphertext = cbc_encrypt(key, iv, msg)?;
+
+    Ok(cyphertext[cyphertext.len() - AES_BLOCK_SIZE..].to_vec())
+}
+
+pub fn verify_cbc_mac(key: &[u8], iv: &[u8], msg: &[u8], mac: &[u8]) -> Result<bool, CipherError> {
+    Ok(cbc_mac(key, iv, msg)? == mac)
+}
+
+// CBC-MAC with the IV fixed at zero, as it should be
+pub fn fixed_iv_cbc_mac(key: &[u8], msg: &[u8]) -> Result<Vec<u8>, CipherError> {
+    cbc_mac(key, &[0; AES_BLOCK_SIZE], msg)
+}
+
+pub fn verify_fixed_iv_cbc_mac(key: &[u8], msg: &[u8], mac: &[u8]) -> Result<bool, CipherError> {
+    Ok(fixed_iv_cbc_mac(key, msg)? == mac)
+}
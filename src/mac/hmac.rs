@@ -0,0 +1,21 @@
+use crate::hash::MerkleDamgard;
+
+// HMAC as specified in RFC 2104, over any Merkle-Damgard hash
+pub fn hmac<H: MerkleDamgard>(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut block_key = if key.len() > H::BLOCK_SIZE {
+        H::hash(key)
+    } else {
+        key.to_vec()
+    };
+    block_key.resize(H::BLOCK_SIZE, 0);
+
+    let mut inner = H::new();
+    inner.update(&block_key.iter().map(|b| b ^ 0x36).collect::<Vec<u8>>());
+    inner.update(msg);
+
+    let mut outer = H::new();
+    outer.update(&block_key.iter().map(|b| b ^ 0x5c).collect::<Vec<u8>>());
+    outer.update(&inner.digest());
+
+    outer.digest()
+}
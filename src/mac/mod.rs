@@ -0,0 +1,3 @@
+pub mod cbc_mac;
+pub mod hmac;
+pub mod secret_prefix;
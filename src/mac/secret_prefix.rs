@@ -0,0 +1,14 @@
+use crate::hash::MerkleDamgard;
+
+// MAC(key, msg) = H(key || msg), which is vulnerable to
+// length extension for any Merkle-Damgard hash H
+pub fn secret_prefix_mac<H: MerkleDamgard>(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut hasher = H::new();
+    hasher.update(key);
+    hasher.update(msg);
+    hasher.digest()
+}
+
+pub fn verify_secret_prefix_mac<H: MerkleDamgard>(key: &[u8], msg: &[u8], mac: &[u8]) -> bool {
+    secret_prefix_mac::<H>(key, msg) == mac
+}
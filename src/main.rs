@@ -2,12 +2,26 @@
 #[macro_use] extern crate failure_derive;
 
 mod user_profile;
+mod user_data;
 mod crack;
 mod cipher;
+mod hash;
+mod mac;
+mod hmac_server;
+mod protocol;
+mod pubkey;
+mod rsa_oracle;
+mod transfer;
+mod compression_oracle;
 mod utils;
+mod clock;
 mod set1;
 mod set2;
 mod set3;
+mod set4;
+mod set5;
+mod set6;
+mod set7;
 
 fn main() {
     println!("Run some tests!");
@@ -0,0 +1,103 @@
+use crate::protocol::transport::Endpoint;
+use crate::protocol::unexpected;
+use crate::pubkey::dh::{decrypt_message, encrypt_message, DhGroup};
+use failure::{format_err, Error};
+use num_bigint::BigUint;
+use rand::Rng;
+
+// A (the initiator) and B (an echo bot) agree on a key with Diffie-Hellman,
+// then B echoes back every message A sends it:
+//
+//   A -> B  Negotiate { p, g }
+//   B -> A  Ack { p, g }        (the group B is going to use)
+//   A -> B  PublicKey(A)
+//   B -> A  PublicKey(B)
+//   A -> B  Data(AES-CBC(SHA1(s)[0..16], iv=random(16), msg) + iv)
+//   B -> A  Data(AES-CBC(SHA1(s)[0..16], iv=random(16), A's msg) + iv)
+//   ...
+//
+// Nothing here is authenticated, so whoever sits on the wire can
+// rewrite any of it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    Negotiate { p: BigUint, g: BigUint },
+    Ack { p: BigUint, g: BigUint },
+    PublicKey(BigUint),
+    Data(Vec<u8>),
+}
+
+// A's side: send each of `msgs` to the echo bot, checking that it
+// sends back the same thing
+pub fn initiator(
+    conn: Endpoint<Message>,
+    group: &DhGroup,
+    msgs: &[&[u8]],
+    rng: &mut impl Rng,
+) -> Result<(), Error> {
+    conn.send(Message::Negotiate {
+        p: group.p.clone(),
+        g: group.g.clone(),
+    })?;
+
+    let group = match conn.recv()? {
+        Message::Ack { p, g } => DhGroup::new(p, g),
+        msg => return Err(unexpected(msg)),
+    };
+
+    let keypair = group.generate_keypair(rng);
+    conn.send(Message::PublicKey(keypair.public.clone()))?;
+
+    let secret = match conn.recv()? {
+        Message::PublicKey(public) => group.shared_secret(&keypair, &public),
+        msg => return Err(unexpected(msg)),
+    };
+
+    for &msg in msgs {
+        conn.send(Message::Data(encrypt_message(&secret, msg, rng)?))?;
+
+        let echo = match conn.recv()? {
+            Message::Data(cyphertext) => decrypt_message(&secret, &cyphertext)?,
+            msg => return Err(unexpected(msg)),
+        };
+
+        if echo != msg {
+            return Err(format_err!("Echo {:?} doesn't match {:?}", echo, msg));
+        }
+    }
+
+    Ok(())
+}
+
+// B's side: agree on a key, then echo messages until A hangs up
+pub fn echo_bot(conn: Endpoint<Message>, rng: &mut impl Rng) -> Result<(), Error> {
+    let group = match conn.recv()? {
+        Message::Negotiate { p, g } => DhGroup::new(p, g),
+        msg => return Err(unexpected(msg)),
+    };
+
+    conn.send(Message::Ack {
+        p: group.p.clone(),
+        g: group.g.clone(),
+    })?;
+
+    let keypair = group.generate_keypair(rng);
+
+    let secret = match conn.recv()? {
+        Message::PublicKey(public) => group.shared_secret(&keypair, &public),
+        msg => return Err(unexpected(msg)),
+    };
+
+    conn.send(Message::PublicKey(keypair.public.clone()))?;
+
+    while let Some(msg) = conn.next_message() {
+        match msg {
+            Message::Data(cyphertext) => {
+                let plaintext = decrypt_message(&secret, &cyphertext)?;
+                conn.send(Message::Data(encrypt_message(&secret, &plaintext, rng)?))?;
+            }
+            msg => return Err(unexpected(msg)),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+pub mod dh_echo;
+pub mod simple_srp;
+pub mod srp;
+pub mod transport;
+
+use failure::{format_err, Error};
+use std::fmt::Debug;
+
+// the error for a party receiving a message it wasn't expecting
+pub fn unexpected<T: Debug>(msg: T) -> Error {
+    format_err!("Unexpected message: {:?}", msg)
+}
@@ -0,0 +1,109 @@
+use crate::protocol::srp::{private_key, proof, session_key, Account};
+use crate::protocol::transport::Endpoint;
+use crate::protocol::unexpected;
+use crate::pubkey::dh::DhGroup;
+use crate::pubkey::math::modexp;
+use failure::Error;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::Rng;
+
+// a simplified SRP, where B doesn't depend on the password verifier,
+// and u is random rather than derived from A and B:
+//
+//   C -> S  Hello { I, A = g^a }
+//   S -> C  Challenge { salt, B = g^b, u }
+//   C -> S  Proof(HMAC-SHA256(K, salt))
+//   S -> C  LoginResult(ok)
+//
+// The client computes S = B^(a + ux), the server S = (A * v^u)^b
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    Hello {
+        email: String,
+        public: BigUint,
+    },
+    Challenge {
+        salt: Vec<u8>,
+        public: BigUint,
+        u: BigUint,
+    },
+    Proof(Vec<u8>),
+    LoginResult(bool),
+}
+
+const SCRAMBLER_BITS: u64 = 128;
+
+pub struct SimpleSrpServer {
+    group: DhGroup,
+    account: Account,
+}
+
+impl SimpleSrpServer {
+    pub fn new(group: DhGroup, email: &str, password: &str, rng: &mut impl Rng) -> SimpleSrpServer {
+        let account = Account::new(&group, email, password, rng);
+        SimpleSrpServer { group, account }
+    }
+
+    // as SrpServer::serve, but for the simplified protocol
+    pub fn serve(&self, conn: Endpoint<Message>, rng: &mut impl Rng) -> Result<bool, Error> {
+        let group = &self.group;
+        let account = &self.account;
+
+        let client_public = match conn.recv()? {
+            Message::Hello { ref email, public } if email == account.email() => public,
+            msg => return Err(unexpected(msg)),
+        };
+
+        let private = rng.gen_biguint_range(&BigUint::one(), &(&group.p - 1u32));
+        let u = rng.gen_biguint(SCRAMBLER_BITS);
+
+        conn.send(Message::Challenge {
+            salt: account.salt().to_vec(),
+            public: modexp(&group.g, &private, &group.p),
+            u: u.clone(),
+        })?;
+
+        let secret = account.secret(group, &client_public, &u, &private);
+
+        let ok = match conn.recv()? {
+            Message::Proof(mac) => account.check_proof(&secret, &mac),
+            msg => return Err(unexpected(msg)),
+        };
+
+        conn.send(Message::LoginResult(ok))?;
+
+        Ok(ok)
+    }
+}
+
+// as srp::login, but for the simplified protocol
+pub fn login(
+    conn: Endpoint<Message>,
+    group: &DhGroup,
+    email: &str,
+    password: &str,
+    rng: &mut impl Rng,
+) -> Result<bool, Error> {
+    let private = rng.gen_biguint_range(&BigUint::one(), &(&group.p - 1u32));
+
+    conn.send(Message::Hello {
+        email: email.to_string(),
+        public: modexp(&group.g, &private, &group.p),
+    })?;
+
+    let (salt, server_public, u) = match conn.recv()? {
+        Message::Challenge { salt, public, u } => (salt, public, u),
+        msg => return Err(unexpected(msg)),
+    };
+
+    let x = private_key(&salt, email, password);
+    let secret = modexp(&server_public, &(private + u * x), &group.p);
+
+    conn.send(Message::Proof(proof(&session_key(&secret), &salt)))?;
+
+    match conn.recv()? {
+        Message::LoginResult(ok) => Ok(ok),
+        msg => Err(unexpected(msg)),
+    }
+}
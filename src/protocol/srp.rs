@@ -0,0 +1,206 @@
+use crate::hash::sha256::{sha256, Sha256};
+use crate::mac::hmac::hmac;
+use crate::protocol::transport::Endpoint;
+use crate::protocol::unexpected;
+use crate::pubkey::dh::DhGroup;
+use crate::pubkey::math::modexp;
+use failure::Error;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::Rng;
+
+// SRP-6a (RFC 5054) over SHA-256, except that the client proves it has
+// the session key with HMAC-SHA256(K, salt), rather than RFC 2945's M1:
+//
+//   C -> S  Hello { I, A = g^a }
+//   S -> C  Challenge { salt, B = kv + g^b }
+//   C -> S  Proof(HMAC-SHA256(K, salt))
+//   S -> C  LoginResult(ok)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    Hello { email: String, public: BigUint },
+    Challenge { salt: Vec<u8>, public: BigUint },
+    Proof(Vec<u8>),
+    LoginResult(bool),
+}
+
+const SALT_SIZE: usize = 16;
+
+// left-pad `n` with zeros to the length of the modulus, as RFC 5054's PAD()
+fn pad(group: &DhGroup, n: &BigUint) -> Vec<u8> {
+    let len = group.p.bits().div_ceil(8) as usize;
+    let bytes = n.to_bytes_be();
+
+    let mut padded = vec![0; len.saturating_sub(bytes.len())];
+    padded.extend(bytes);
+    padded
+}
+
+fn hash_to_int(data: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&sha256(data))
+}
+
+// k = H(N | PAD(g))
+pub fn multiplier(group: &DhGroup) -> BigUint {
+    let mut data = group.p.to_bytes_be();
+    data.extend(pad(group, &group.g));
+    hash_to_int(&data)
+}
+
+// u = H(PAD(A) | PAD(B))
+pub fn scrambler(group: &DhGroup, client_public: &BigUint, server_public: &BigUint) -> BigUint {
+    let mut data = pad(group, client_public);
+    data.extend(pad(group, server_public));
+    hash_to_int(&data)
+}
+
+// x = H(salt | H(I | ":" | P))
+pub fn private_key(salt: &[u8], email: &str, password: &str) -> BigUint {
+    let mut data = salt.to_vec();
+    data.extend(sha256(format!("{}:{}", email, password).as_bytes()));
+    hash_to_int(&data)
+}
+
+// K = H(S)
+pub fn session_key(secret: &BigUint) -> Vec<u8> {
+    sha256(&secret.to_bytes_be())
+}
+
+pub fn proof(session_key: &[u8], salt: &[u8]) -> Vec<u8> {
+    hmac::<Sha256>(session_key, salt)
+}
+
+// what a server keeps for a user: a salt and password verifier,
+// rather than the password itself
+pub struct Account {
+    email: String,
+    salt: Vec<u8>,
+    verifier: BigUint,
+}
+
+impl Account {
+    pub fn new(group: &DhGroup, email: &str, password: &str, rng: &mut impl Rng) -> Account {
+        let salt: Vec<u8> = (0..SALT_SIZE).map(|_| rng.gen::<u8>()).collect();
+        let verifier = modexp(&group.g, &private_key(&salt, email, password), &group.p);
+
+        Account {
+            email: email.to_string(),
+            salt,
+            verifier,
+        }
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+
+    pub fn verifier(&self) -> &BigUint {
+        &self.verifier
+    }
+
+    // the server's S = (A * v^u)^b
+    pub fn secret(
+        &self,
+        group: &DhGroup,
+        client_public: &BigUint,
+        u: &BigUint,
+        private: &BigUint,
+    ) -> BigUint {
+        modexp(
+            &(client_public * modexp(&self.verifier, u, &group.p)),
+            private,
+            &group.p,
+        )
+    }
+
+    // whether the client's proof shows it has the same session key
+    pub fn check_proof(&self, secret: &BigUint, client_proof: &[u8]) -> bool {
+        client_proof == proof(&session_key(secret), &self.salt).as_slice()
+    }
+}
+
+// a server which knows a single user
+pub struct SrpServer {
+    group: DhGroup,
+    account: Account,
+}
+
+impl SrpServer {
+    pub fn new(group: DhGroup, email: &str, password: &str, rng: &mut impl Rng) -> SrpServer {
+        let account = Account::new(&group, email, password, rng);
+        SrpServer { group, account }
+    }
+
+    // handle a single login attempt, returning whether it succeeded
+    pub fn serve(&self, conn: Endpoint<Message>, rng: &mut impl Rng) -> Result<bool, Error> {
+        let group = &self.group;
+        let account = &self.account;
+
+        let client_public = match conn.recv()? {
+            Message::Hello { ref email, public } if email == account.email() => public,
+            msg => return Err(unexpected(msg)),
+        };
+
+        let private = rng.gen_biguint_range(&BigUint::one(), &(&group.p - 1u32));
+        let public = (multiplier(group) * account.verifier()
+            + modexp(&group.g, &private, &group.p))
+            % &group.p;
+
+        conn.send(Message::Challenge {
+            salt: account.salt().to_vec(),
+            public: public.clone(),
+        })?;
+
+        let u = scrambler(group, &client_public, &public);
+        let secret = account.secret(group, &client_public, &u, &private);
+
+        let ok = match conn.recv()? {
+            Message::Proof(mac) => account.check_proof(&secret, &mac),
+            msg => return Err(unexpected(msg)),
+        };
+
+        conn.send(Message::LoginResult(ok))?;
+
+        Ok(ok)
+    }
+}
+
+// log in to the server on `conn`, returning whether it let us in
+pub fn login(
+    conn: Endpoint<Message>,
+    group: &DhGroup,
+    email: &str,
+    password: &str,
+    rng: &mut impl Rng,
+) -> Result<bool, Error> {
+    let private = rng.gen_biguint_range(&BigUint::one(), &(&group.p - 1u32));
+    let public = modexp(&group.g, &private, &group.p);
+
+    conn.send(Message::Hello {
+        email: email.to_string(),
+        public: public.clone(),
+    })?;
+
+    let (salt, server_public) = match conn.recv()? {
+        Message::Challenge { salt, public } => (salt, public),
+        msg => return Err(unexpected(msg)),
+    };
+
+    // S = (B - k * g^x)^(a + u * x)
+    let x = private_key(&salt, email, password);
+    let u = scrambler(group, &public, &server_public);
+    let kgx = multiplier(group) * modexp(&group.g, &x, &group.p) % &group.p;
+    let base = (server_public % &group.p + &group.p - kgx) % &group.p;
+    let secret = modexp(&base, &(private + u * x), &group.p);
+
+    conn.send(Message::Proof(proof(&session_key(&secret), &salt)))?;
+
+    match conn.recv()? {
+        Message::LoginResult(ok) => Ok(ok),
+        msg => Err(unexpected(msg)),
+    }
+}
@@ -0,0 +1,39 @@
+use failure::{format_err, Error};
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+// one end of an in-memory, bidirectional connection between two
+// parties, each typically running on its own thread. Dropping an
+// endpoint hangs up. Putting an attacker in the middle is just a
+// matter of handing them one end of two separate connections
+pub struct Endpoint<T> {
+    tx: Sender<T>,
+    rx: Receiver<T>,
+}
+
+// a connected pair of endpoints
+pub fn connection<T>() -> (Endpoint<T>, Endpoint<T>) {
+    let (a_tx, b_rx) = channel();
+    let (b_tx, a_rx) = channel();
+
+    (
+        Endpoint { tx: a_tx, rx: a_rx },
+        Endpoint { tx: b_tx, rx: b_rx },
+    )
+}
+
+impl<T> Endpoint<T> {
+    pub fn send(&self, msg: T) -> Result<(), Error> {
+        self.tx.send(msg).map_err(|_| format_err!("Peer hung up"))
+    }
+
+    // blocks until the peer sends something, or hangs up
+    pub fn recv(&self) -> Result<T, Error> {
+        self.rx.recv().map_err(|_| format_err!("Peer hung up"))
+    }
+
+    // like recv, but a hang up is reported as None rather than an
+    // error, for parties that serve until the other side is done
+    pub fn next_message(&self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
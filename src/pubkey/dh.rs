@@ -0,0 +1,79 @@
+use crate::cipher::aes::{cbc_decrypt, cbc_encrypt, CipherError, AES_BLOCK_SIZE};
+use crate::hash::sha1::sha1;
+use crate::pubkey::math::modexp;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::Rng;
+
+// the 1536-bit MODP group from RFC 3526, as used by NIST
+const NIST_P: &str = "\
+    ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74\
+    020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f1437\
+    4fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7ed\
+    ee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf05\
+    98da48361c55d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552bb\
+    9ed529077096966d670c354e4abc9804f1746c08ca237327ffffffffffffffff";
+const NIST_G: u32 = 2;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DhGroup {
+    pub p: BigUint,
+    pub g: BigUint,
+}
+
+#[derive(Clone, Debug)]
+pub struct DhKeyPair {
+    pub private: BigUint,
+    pub public: BigUint,
+}
+
+impl DhGroup {
+    pub fn new(p: BigUint, g: BigUint) -> DhGroup {
+        DhGroup { p, g }
+    }
+
+    pub fn nist() -> DhGroup {
+        DhGroup {
+            p: BigUint::parse_bytes(NIST_P.as_bytes(), 16).unwrap(),
+            g: BigUint::from(NIST_G),
+        }
+    }
+
+    // private key drawn uniformly from [1, p - 1)
+    pub fn generate_keypair(&self, rng: &mut impl Rng) -> DhKeyPair {
+        let private = rng.gen_biguint_range(&BigUint::one(), &(&self.p - 1u32));
+        let public = modexp(&self.g, &private, &self.p);
+
+        DhKeyPair { private, public }
+    }
+
+    pub fn shared_secret(&self, keypair: &DhKeyPair, other_public: &BigUint) -> BigUint {
+        modexp(other_public, &keypair.private, &self.p)
+    }
+}
+
+// first 16 bytes of the SHA-1 of the (big-endian) shared secret
+pub fn derive_aes_key(secret: &BigUint) -> Vec<u8> {
+    sha1(&secret.to_bytes_be())[..16].to_vec()
+}
+
+// AES-CBC under a key derived from `secret`, with a random IV appended
+// to the cyphertext
+pub fn encrypt_message(
+    secret: &BigUint,
+    msg: &[u8],
+    rng: &mut impl Rng,
+) -> Result<Vec<u8>, CipherError> {
+    let iv: Vec<u8> = (0..AES_BLOCK_SIZE).map(|_| rng.gen::<u8>()).collect();
+
+    let mut cyphertext = cbc_encrypt(&derive_aes_key(secret), &iv, msg)?;
+    cyphertext.extend(iv);
+
+    Ok(cyphertext)
+}
+
+pub fn decrypt_message(secret: &BigUint, cyphertext: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let (cyphertext, iv) = cyphertext.split_at(cyphertext.len().saturating_sub(AES_BLOCK_SIZE));
+
+    cbc_decrypt(&derive_aes_key(secret), iv, cyphertext)
+}
@@ -0,0 +1,134 @@
+// DSA (FIPS 186) with SHA-1 as the message hash
+use crate::hash::sha1::sha1;
+use crate::pubkey::math::{generate_prime, invmod, is_probable_prime, modexp};
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::Rng;
+
+// the 1024/160 bit parameters used in the cryptopals challenges
+const STANDARD_P: &str = "\
+    800000000000000089e1855218a0e7dac38136ffafa72eda7859f2171e25e65eac698c1702578b07dc2a1076da2\
+    41c76c62d374d8389ea5aeffd3226a0530cc565f3bf6b50929139ebeac04f48c3c84afb796d61e5a4f9a8fda812\
+    ab59494232c7d2b4deb50aa18ee9e132bfa85ac4374d7f9091abc3d015efc871a584471bb1";
+const STANDARD_Q: &str = "f4f47f05794b256174bba6e9b396a7707e563c5b";
+const STANDARD_G: &str = "\
+    5958c9d3898b224b12672c0b98e06c60df923cb8bc999d119458fef538b8fa4046c8db53039db620c094c9fa077\
+    ef389b5322a559946a71903f990f1f7e0e025e2d7f7cf494aff1a0470f5b64c36b625a097f1651fe775323556fe\
+    00b3608c887892878480e99041be601a62166ca6894bdd41a7054ec89f756ba9fc95302291";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DsaParams {
+    pub p: BigUint,
+    pub q: BigUint,
+    pub g: BigUint,
+}
+
+#[derive(Clone, Debug)]
+pub struct DsaKeyPair {
+    pub x: BigUint,
+    pub y: BigUint,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DsaSignature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+// H(m), as an integer
+pub fn message_hash(msg: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&sha1(msg))
+}
+
+impl DsaParams {
+    pub fn standard() -> DsaParams {
+        DsaParams {
+            p: BigUint::parse_bytes(STANDARD_P.as_bytes(), 16).unwrap(),
+            q: BigUint::parse_bytes(STANDARD_Q.as_bytes(), 16).unwrap(),
+            g: BigUint::parse_bytes(STANDARD_G.as_bytes(), 16).unwrap(),
+        }
+    }
+
+    // a `q_bits` bit prime q, a `p_bits` bit prime p = kq + 1,
+    // and a generator g of the order q subgroup mod p
+    pub fn generate(p_bits: u64, q_bits: u64, rng: &mut impl Rng) -> DsaParams {
+        let q = generate_prime(q_bits, rng);
+
+        let low = BigUint::one() << (p_bits - 1);
+        let high = BigUint::one() << p_bits;
+
+        let p = loop {
+            let candidate = rng.gen_biguint_range(&low, &high);
+            // round down to one more than a multiple of q
+            let p = &candidate - (&candidate % &q) + 1u32;
+            if p >= low && is_probable_prime(&p, rng) {
+                break p;
+            }
+        };
+
+        let exponent = (&p - 1u32) / &q;
+        let g = loop {
+            let h = rng.gen_biguint_range(&BigUint::from(2u32), &(&p - 1u32));
+            let g = modexp(&h, &exponent, &p);
+            if !g.is_one() {
+                break g;
+            }
+        };
+
+        DsaParams { p, q, g }
+    }
+
+    // checks p and q are prime, q divides p - 1, and g has order q
+    pub fn is_valid(&self, rng: &mut impl Rng) -> bool {
+        is_probable_prime(&self.p, rng)
+            && is_probable_prime(&self.q, rng)
+            && ((&self.p - 1u32) % &self.q).is_zero()
+            && self.g > BigUint::one()
+            && modexp(&self.g, &self.q, &self.p).is_one()
+    }
+
+    pub fn generate_keypair(&self, rng: &mut impl Rng) -> DsaKeyPair {
+        let x = rng.gen_biguint_range(&BigUint::one(), &self.q);
+        let y = modexp(&self.g, &x, &self.p);
+
+        DsaKeyPair { x, y }
+    }
+
+    // sign with a given nonce `k`. Doesn't reject r = 0 or s = 0,
+    // which lets it go along with a tampered g
+    pub fn sign_with_nonce(&self, x: &BigUint, msg: &[u8], k: &BigUint) -> DsaSignature {
+        let r = modexp(&self.g, k, &self.p) % &self.q;
+
+        // a k that isn't invertible is a broken nonce, and makes for a broken s
+        let k_inv = invmod(k, &self.q).unwrap_or_else(BigUint::zero);
+        let s = k_inv * (message_hash(msg) + x * &r) % &self.q;
+
+        DsaSignature { r, s }
+    }
+
+    pub fn sign(&self, x: &BigUint, msg: &[u8], rng: &mut impl Rng) -> DsaSignature {
+        let k = rng.gen_biguint_range(&BigUint::one(), &self.q);
+        self.sign_with_nonce(x, msg, &k)
+    }
+
+    // verification, minus the check that 0 < r < q and 0 < s < q
+    pub fn verify_unchecked(&self, y: &BigUint, msg: &[u8], signature: &DsaSignature) -> bool {
+        let w = match invmod(&signature.s, &self.q) {
+            Some(w) => w,
+            None => return false,
+        };
+
+        let u1 = message_hash(msg) * &w % &self.q;
+        let u2 = &signature.r * &w % &self.q;
+
+        let v = modexp(&self.g, &u1, &self.p) * modexp(y, &u2, &self.p) % &self.p % &self.q;
+
+        v == signature.r
+    }
+
+    pub fn verify(&self, y: &BigUint, msg: &[u8], signature: &DsaSignature) -> bool {
+        let in_range = |n: &BigUint| !n.is_zero() && *n < self.q;
+
+        in_range(&signature.r) && in_range(&signature.s) && self.verify_unchecked(y, msg, signature)
+    }
+}
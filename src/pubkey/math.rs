@@ -0,0 +1,141 @@
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::Rng;
+
+// right-to-left square-and-multiply
+pub fn modexp(base: &BigUint, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+    if modulus.is_one() {
+        return BigUint::zero();
+    }
+
+    let mut result = BigUint::one();
+    let mut base = base % modulus;
+
+    for i in 0..exponent.bits() {
+        if exponent.bit(i) {
+            result = (&result * &base) % modulus;
+        }
+        base = (&base * &base) % modulus;
+    }
+
+    result
+}
+
+// multiplicative inverse of `a` mod `m`, if there is one, by the extended
+// Euclidean algorithm
+pub fn invmod(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    let m_int = BigInt::from(m.clone());
+
+    let (mut old_r, mut r) = (BigInt::from(a % m), m_int.clone());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+
+    while !r.is_zero() {
+        let quotient = &old_r / &r;
+
+        let new_r = &old_r - &quotient * &r;
+        old_r = std::mem::replace(&mut r, new_r);
+
+        let new_s = &old_s - &quotient * &s;
+        old_s = std::mem::replace(&mut s, new_s);
+    }
+
+    if !old_r.is_one() {
+        return None;
+    }
+
+    old_s.mod_floor(&m_int).to_biguint()
+}
+
+// floor of the `k`th root of `n`, by Newton's method
+pub fn nth_root(n: &BigUint, k: u32) -> BigUint {
+    if n.is_zero() {
+        return BigUint::zero();
+    }
+
+    // start from a power of two that's definitely too big, and come down
+    let mut x = BigUint::one() << (n.bits() / k as u64 + 1);
+
+    loop {
+        let y = ((k - 1) * &x + n / x.pow(k - 1)) / k;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+// the unique x mod (m_1 * m_2 * ...) with x = r_i mod m_i for each
+// (r_i, m_i) in `congruences`. The moduli must be pairwise coprime
+pub fn crt(congruences: &[(BigUint, BigUint)]) -> Option<BigUint> {
+    let product: BigUint = congruences.iter().map(|(_, m)| m).product();
+
+    let mut result = BigUint::zero();
+    for (r, m) in congruences {
+        let m_s = &product / m;
+        result += r * &m_s * invmod(&m_s, m)?;
+    }
+
+    Some(result % product)
+}
+
+const SMALL_PRIMES: [u32; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+const MILLER_RABIN_ROUNDS: usize = 32;
+
+// Miller-Rabin, after some trial division
+pub fn is_probable_prime(n: &BigUint, rng: &mut impl Rng) -> bool {
+    let two = BigUint::from(2u32);
+
+    if *n < two {
+        return false;
+    }
+    for &p in SMALL_PRIMES.iter() {
+        if *n == BigUint::from(p) {
+            return true;
+        }
+        if (n % p).is_zero() {
+            return false;
+        }
+    }
+
+    // n - 1 = 2^s * d, with d odd
+    let n_minus_one = n - 1u32;
+    let s = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> s;
+
+    'witness: for _ in 0..MILLER_RABIN_ROUNDS {
+        let a = rng.gen_biguint_range(&two, &n_minus_one);
+        let mut x = modexp(&a, &d, n);
+
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = modexp(&x, &two, n);
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+// a random `bits` bit prime, with the top two bits set, so that the
+// product of two such primes has exactly 2 * `bits` bits
+pub fn generate_prime(bits: u64, rng: &mut impl Rng) -> BigUint {
+    loop {
+        let mut candidate = rng.gen_biguint(bits);
+        candidate.set_bit(bits - 1, true);
+        candidate.set_bit(bits - 2, true);
+        candidate.set_bit(0, true);
+
+        if is_probable_prime(&candidate, rng) {
+            return candidate;
+        }
+    }
+}
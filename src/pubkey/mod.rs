@@ -0,0 +1,5 @@
+pub mod dh;
+pub mod dsa;
+pub mod math;
+pub mod pkcs1;
+pub mod rsa;
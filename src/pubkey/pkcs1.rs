@@ -0,0 +1,105 @@
+// PKCS#1 v1.5 (RFC 8017) signatures, with SHA-1, and encryption padding
+use crate::hash::sha1::sha1;
+use crate::pubkey::rsa::{to_bytes_padded, RsaPrivateKey, RsaPublicKey};
+use num_bigint::BigUint;
+use rand::Rng;
+
+// DER encoding of the DigestInfo header for a SHA-1 digest
+pub const SHA1_DIGEST_INFO: [u8; 15] = [
+    0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14,
+];
+
+// 00 01 ff .. ff 00 DigestInfo H(msg), as long as the modulus
+pub fn encode_signature_block(msg: &[u8], len: usize) -> Vec<u8> {
+    let mut digest_info = SHA1_DIGEST_INFO.to_vec();
+    digest_info.extend(sha1(msg));
+
+    let mut block = vec![0x00, 0x01];
+    block.extend(vec![0xff; len.saturating_sub(digest_info.len() + 3)]);
+    block.push(0x00);
+    block.extend(digest_info);
+    block
+}
+
+pub fn sign(private: &RsaPrivateKey, msg: &[u8]) -> Vec<u8> {
+    let block = encode_signature_block(msg, private.size());
+    let signature = private.decrypt(&BigUint::from_bytes_be(&block));
+
+    to_bytes_padded(&signature, private.size())
+}
+
+fn signature_block(public: &RsaPublicKey, signature: &[u8]) -> Vec<u8> {
+    let block = public.encrypt(&BigUint::from_bytes_be(signature));
+    to_bytes_padded(&block, public.size())
+}
+
+// checks the block starts 00 01 ff .. ff 00 DigestInfo H(msg), but not
+// that the padding runs all the way up to the hash, so anything at
+// all can follow it
+pub fn verify_sloppy(public: &RsaPublicKey, msg: &[u8], signature: &[u8]) -> bool {
+    let block = signature_block(public, signature);
+
+    if !block.starts_with(&[0x00, 0x01]) {
+        return false;
+    }
+
+    let padding_len = block[2..].iter().take_while(|&&b| b == 0xff).count();
+    if padding_len == 0 {
+        return false;
+    }
+
+    let rest = &block[2 + padding_len..];
+    if !rest.starts_with(&[0x00]) || !rest[1..].starts_with(&SHA1_DIGEST_INFO) {
+        return false;
+    }
+
+    let hash = &rest[1 + SHA1_DIGEST_INFO.len()..];
+    hash.starts_with(&sha1(msg))
+}
+
+// the right way: re-encode the expected block and compare the whole thing
+pub fn verify_strict(public: &RsaPublicKey, msg: &[u8], signature: &[u8]) -> bool {
+    signature_block(public, signature) == encode_signature_block(msg, public.size())
+}
+
+// smallest number of random padding bytes in an encryption block
+const MIN_PADDING_LEN: usize = 8;
+
+// 00 02 <random non-zero bytes> 00 msg, as long as the modulus
+pub fn pad_encryption_block(msg: &[u8], len: usize, rng: &mut impl Rng) -> Option<Vec<u8>> {
+    let padding_len = len.checked_sub(msg.len() + 3)?;
+    if padding_len < MIN_PADDING_LEN {
+        return None;
+    }
+
+    let mut block = vec![0x00, 0x02];
+    block.extend((0..padding_len).map(|_| rng.gen_range(1, 256) as u8));
+    block.push(0x00);
+    block.extend(msg);
+    Some(block)
+}
+
+pub fn unpad_encryption_block(block: &[u8]) -> Option<Vec<u8>> {
+    if !block.starts_with(&[0x00, 0x02]) {
+        return None;
+    }
+
+    let separator = block[2..].iter().position(|&b| b == 0x00)? + 2;
+    if separator - 2 < MIN_PADDING_LEN {
+        return None;
+    }
+
+    Some(block[separator + 1..].to_vec())
+}
+
+// None if `msg` is too long for the key
+pub fn encrypt(public: &RsaPublicKey, msg: &[u8], rng: &mut impl Rng) -> Option<Vec<u8>> {
+    let block = pad_encryption_block(msg, public.size(), rng)?;
+    Some(public.encrypt_bytes(&block))
+}
+
+// None if the cyphertext doesn't decrypt to a validly padded block
+pub fn decrypt(private: &RsaPrivateKey, cyphertext: &[u8]) -> Option<Vec<u8>> {
+    let block = private.decrypt(&BigUint::from_bytes_be(cyphertext));
+    unpad_encryption_block(&to_bytes_padded(&block, private.size()))
+}
@@ -0,0 +1,138 @@
+// textbook (unpadded) RSA
+use crate::pubkey::math::{generate_prime, invmod, modexp};
+use num_bigint::BigUint;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaPublicKey {
+    pub e: BigUint,
+    pub n: BigUint,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaPrivateKey {
+    pub d: BigUint,
+    pub n: BigUint,
+    // kept around to decrypt mod p and q separately, which is several
+    // times faster than working mod n (and the padding oracle attacks
+    // decrypt a lot)
+    crt: CrtComponents,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct CrtComponents {
+    p: BigUint,
+    q: BigUint,
+    d_p: BigUint,
+    d_q: BigUint,
+    q_inv: BigUint,
+}
+
+#[derive(Clone, Debug)]
+pub struct RsaKeyPair {
+    pub public: RsaPublicKey,
+    pub private: RsaPrivateKey,
+}
+
+impl RsaKeyPair {
+    // a key pair with a `bits` bit modulus and public exponent `e`
+    pub fn generate(bits: u64, e: u32, rng: &mut impl Rng) -> RsaKeyPair {
+        let e = BigUint::from(e);
+
+        loop {
+            let p = generate_prime(bits / 2, rng);
+            let q = generate_prime(bits - bits / 2, rng);
+            if p == q {
+                continue;
+            }
+
+            let totient = (&p - 1u32) * (&q - 1u32);
+
+            // e has to be invertible mod the totient, otherwise pick new primes
+            if let Some(d) = invmod(&e, &totient) {
+                let n = &p * &q;
+                let crt = CrtComponents {
+                    d_p: &d % (&p - 1u32),
+                    d_q: &d % (&q - 1u32),
+                    q_inv: invmod(&q, &p).unwrap(),
+                    p,
+                    q,
+                };
+
+                return RsaKeyPair {
+                    public: RsaPublicKey { e, n: n.clone() },
+                    private: RsaPrivateKey { d, n, crt },
+                };
+            }
+        }
+    }
+}
+
+// like crack::aes::deterministic_key, for reproducible tests
+pub fn deterministic_keypair(bits: u64, e: u32, seed: u64) -> RsaKeyPair {
+    let mut rng: StdRng = SeedableRng::seed_from_u64(seed);
+
+    RsaKeyPair::generate(bits, e, &mut rng)
+}
+
+// size of the modulus in bytes
+fn modulus_size(n: &BigUint) -> usize {
+    n.bits().div_ceil(8) as usize
+}
+
+// big-endian bytes of `m`, left-padded with zeros to `len`
+pub fn to_bytes_padded(m: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = m.to_bytes_be();
+
+    let mut padded = vec![0; len.saturating_sub(bytes.len())];
+    padded.extend(bytes);
+    padded
+}
+
+impl RsaPublicKey {
+    pub fn encrypt(&self, m: &BigUint) -> BigUint {
+        modexp(m, &self.e, &self.n)
+    }
+
+    // the cyphertext is as long as the modulus
+    pub fn encrypt_bytes(&self, msg: &[u8]) -> Vec<u8> {
+        let c = self.encrypt(&BigUint::from_bytes_be(msg));
+        to_bytes_padded(&c, self.size())
+    }
+
+    pub fn size(&self) -> usize {
+        modulus_size(&self.n)
+    }
+}
+
+impl RsaPrivateKey {
+    // c^d mod n, via m_p = c^d mod p and m_q = c^d mod q. This uses the
+    // bignum library's (Montgomery) modpow, rather than our modexp, as
+    // it's the hot path of the padding oracle attacks
+    pub fn decrypt(&self, c: &BigUint) -> BigUint {
+        let CrtComponents {
+            p,
+            q,
+            d_p,
+            d_q,
+            q_inv,
+        } = &self.crt;
+
+        let m_p = c.modpow(d_p, p);
+        let m_q = c.modpow(d_q, q);
+        let h = q_inv * (m_p + p - &m_q % p) % p;
+
+        m_q + h * q
+    }
+
+    // leading zero bytes of the plaintext don't survive the round trip
+    pub fn decrypt_bytes(&self, cyphertext: &[u8]) -> Vec<u8> {
+        self.decrypt(&BigUint::from_bytes_be(cyphertext))
+            .to_bytes_be()
+    }
+
+    pub fn size(&self) -> usize {
+        modulus_size(&self.n)
+    }
+}
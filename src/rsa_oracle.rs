@@ -0,0 +1,101 @@
+use crate::hash::sha256::sha256;
+use crate::pubkey::rsa::{to_bytes_padded, RsaKeyPair, RsaPublicKey};
+use failure::{format_err, Error};
+use num_bigint::BigUint;
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+// a service holding an RSA private key, which will decrypt
+// (some) cyphertexts under the corresponding public key for us
+pub trait DecryptionOracle {
+    fn public_key(&self) -> &RsaPublicKey;
+    fn decrypt(&self, cyphertext: &BigUint) -> Result<BigUint, Error>;
+}
+
+// decrypts anything, but only once: it remembers the hash of every
+// cyphertext it's been given, and refuses to decrypt any of them again
+pub struct OnceOnlyDecryptionServer {
+    keypair: RsaKeyPair,
+    seen: RefCell<HashSet<Vec<u8>>>,
+}
+
+impl OnceOnlyDecryptionServer {
+    pub fn new(keypair: RsaKeyPair) -> OnceOnlyDecryptionServer {
+        OnceOnlyDecryptionServer {
+            keypair,
+            seen: RefCell::new(HashSet::new()),
+        }
+    }
+}
+
+impl DecryptionOracle for OnceOnlyDecryptionServer {
+    fn public_key(&self) -> &RsaPublicKey {
+        &self.keypair.public
+    }
+
+    fn decrypt(&self, cyphertext: &BigUint) -> Result<BigUint, Error> {
+        let hash = sha256(&cyphertext.to_bytes_be());
+
+        if !self.seen.borrow_mut().insert(hash) {
+            return Err(format_err!("Already decrypted that cyphertext"));
+        }
+
+        Ok(self.keypair.private.decrypt(cyphertext))
+    }
+}
+
+// tells us whether a cyphertext decrypts to an even plaintext
+pub trait ParityOracle {
+    fn public_key(&self) -> &RsaPublicKey;
+    fn is_even(&self, cyphertext: &BigUint) -> bool;
+}
+
+pub struct ParityServer {
+    keypair: RsaKeyPair,
+}
+
+impl ParityServer {
+    pub fn new(keypair: RsaKeyPair) -> ParityServer {
+        ParityServer { keypair }
+    }
+}
+
+impl ParityOracle for ParityServer {
+    fn public_key(&self) -> &RsaPublicKey {
+        &self.keypair.public
+    }
+
+    fn is_even(&self, cyphertext: &BigUint) -> bool {
+        !self.keypair.private.decrypt(cyphertext).bit(0)
+    }
+}
+
+// tells us whether a cyphertext decrypts to something that starts like
+// a PKCS#1 v1.5 encryption block (00 02), without checking the rest
+pub trait PaddingOracle {
+    fn public_key(&self) -> &RsaPublicKey;
+    fn is_conforming(&self, cyphertext: &BigUint) -> bool;
+}
+
+pub struct PaddingServer {
+    keypair: RsaKeyPair,
+}
+
+impl PaddingServer {
+    pub fn new(keypair: RsaKeyPair) -> PaddingServer {
+        PaddingServer { keypair }
+    }
+}
+
+impl PaddingOracle for PaddingServer {
+    fn public_key(&self) -> &RsaPublicKey {
+        &self.keypair.public
+    }
+
+    fn is_conforming(&self, cyphertext: &BigUint) -> bool {
+        let private = &self.keypair.private;
+        let block = to_bytes_padded(&private.decrypt(cyphertext), private.size());
+
+        block.starts_with(&[0x00, 0x02])
+    }
+}
@@ -193,58 +193,22 @@ fn exercise_15() {
 
 #[test]
 fn exercise_16() {
-    use crate::cipher::aes::{cbc_decrypt, cbc_encrypt};
     use crate::crack::aes::deterministic_key;
+    use crate::crack::user_data::make_admin_user_data;
+    use crate::user_data::{CbcUserData, UserDataOracle};
     use rand::Rng;
 
-    fn is_admin(key: &[u8], iv: &[u8], encrypted_str: &[u8]) -> bool {
-        let decrypted_bytes =
-            cbc_decrypt(key, iv, encrypted_str).expect("Should be able to decrypt cyphertext");
-        let decrypted_str = String::from_utf8_lossy(&decrypted_bytes);
-
-        decrypted_str
-            .split(";")
-            .map(|kv| {
-                let kv_list: Vec<&str> = kv.splitn(2, "=").collect();
-                (kv_list[0], kv_list[1])
-            })
-            .any(|tup| tup == ("admin", "true"))
-    }
-
-    fn make_user(input: &str) -> String {
-        let escaped_input = input.replace(";", "%3B").replace("=", "%3D");
-        format!(
-            "comment1=cooking%20MCs;\
-             userdata={};\
-             comment2=%20like%20a%20pound%20of%20bacon",
-            escaped_input
-        )
-    }
-
-    let mut attacker_vec = vec![b'0'; 16];
-    attacker_vec.extend(":admin<true".as_bytes());
-    let attacker_str = String::from_utf8(attacker_vec).unwrap();
-
-    let user_str = make_user(&attacker_str);
-
     let mut rng = rand::thread_rng();
     let key = deterministic_key(16, 999);
     let iv = rng.gen::<[u8; 16]>();
 
-    let encrypted_user = cbc_encrypt(&key, &iv, &user_str.as_bytes()).unwrap();
-
-    let bitflipped: Vec<u8> = encrypted_user
-        .iter()
-        .enumerate()
-        .map(|(i, &val)| {
-            // bitflip so the ':' and '<' chars in the plaintext become ';' and '='
-            if i == 32 || i == 38 {
-                val ^ 1
-            } else {
-                val
-            }
-        })
-        .collect();
-
-    assert_eq!(is_admin(&key, &iv, &bitflipped), true);
+    let oracle = CbcUserData::new(&key, &iv);
+
+    // verify that the oracle escapes attempts to inject the admin role directly
+    let encrypted_user = oracle.encrypt_user_data(";admin=true;").unwrap();
+    assert!(!oracle.is_admin(&encrypted_user).unwrap());
+
+    let bitflipped = make_admin_user_data(&oracle).unwrap();
+
+    assert!(oracle.is_admin(&bitflipped).unwrap());
 }
@@ -211,3 +211,139 @@ fn exercise_20() {
     assert_eq!(&plaintext[..33], "I'm rated \"R\"...this is a warning");
 
 }
+
+#[test]
+fn exercise_21() {
+    use crate::cipher::mt19937::MT19937;
+
+    // reference output from mt19937ar.out (init_by_array({0x123, 0x234, 0x345, 0x456}))
+    let mut mt = MT19937::seed_by_array(&[0x123, 0x234, 0x345, 0x456]);
+    let outputs: Vec<u32> = (0..10).map(|_| mt.next_u32()).collect();
+
+    assert_eq!(
+        outputs,
+        vec![
+            1067595299, 955945823, 477289528, 4107218783, 4228976476, 3344332714, 3355579695,
+            227628506, 810200273, 2591290167
+        ]
+    );
+
+    // the default seed used by the reference implementation and C++'s std::mt19937,
+    // whose 10000th output is mandated by the standard
+    let mut mt = MT19937::seed(5489);
+    assert_eq!(mt.next_u32(), 3499211612);
+    let ten_thousandth = (1..10000).map(|_| mt.next_u32()).last().unwrap();
+    assert_eq!(ten_thousandth, 4123659995);
+
+    // a generator rebuilt from a copy of the internal state continues the same sequence
+    let mut mt = MT19937::seed(1131464071);
+    let mut copied = MT19937::from_state(*mt.state());
+    for _ in 0..1000 {
+        assert_eq!(mt.next_u32(), copied.next_u32());
+    }
+}
+
+#[test]
+fn exercise_22() {
+    use crate::cipher::mt19937::MT19937;
+    use crate::clock::{Clock, MockClock};
+    use crate::crack::mt19937::{recover_timestamp_seed, timestamp_seeded_oracle};
+    use rand::prelude::StdRng;
+    use rand::SeedableRng;
+
+    let start = 1_571_000_000;
+    let clock = MockClock::new(start);
+    let mut rng: StdRng = SeedableRng::seed_from_u64(22);
+
+    let output = timestamp_seeded_oracle(&clock, &mut rng);
+
+    // the oracle has "waited" 80 to 2000 seconds in total, without actually sleeping
+    assert!(clock.now() >= start + 80 && clock.now() < start + 2000);
+
+    let seed = recover_timestamp_seed(output, &clock, 2000).unwrap();
+
+    assert!(seed > start && seed < clock.now());
+    assert_eq!(MT19937::seed(seed).next_u32(), output);
+}
+
+#[test]
+fn exercise_23() {
+    use crate::cipher::mt19937::{temper, MT19937};
+    use crate::crack::mt19937::{clone_mt19937, untemper};
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..1000 {
+        let y = rng.gen::<u32>();
+        assert_eq!(untemper(temper(y)), y);
+    }
+
+    let mut mt = MT19937::seed(rng.gen::<u32>());
+
+    // throw away a few outputs, so we're not starting right on a twist
+    for _ in 0..rng.gen_range(0, 1000) {
+        mt.next_u32();
+    }
+
+    let outputs: Vec<u32> = (0..624).map(|_| mt.next_u32()).collect();
+    let mut cloned = clone_mt19937(&outputs).unwrap();
+
+    for _ in 0..2000 {
+        assert_eq!(cloned.next_u32(), mt.next_u32());
+    }
+
+    assert!(clone_mt19937(&outputs[..623]).is_err());
+
+    // given more than 624 outputs, the clone picks up after the last of them
+    let outputs: Vec<u32> = (0..1000).map(|_| mt.next_u32()).collect();
+    let mut cloned = clone_mt19937(&outputs).unwrap();
+
+    for _ in 0..2000 {
+        assert_eq!(cloned.next_u32(), mt.next_u32());
+    }
+
+    let mut tampered = outputs.clone();
+    tampered[900] ^= 1;
+    assert!(clone_mt19937(&tampered).is_err());
+}
+
+#[test]
+fn exercise_24() {
+    use crate::cipher::mt19937::mt19937_cipher;
+    use crate::clock::{Clock, MockClock};
+    use crate::crack::mt19937::{
+        is_time_seeded_token, password_reset_token, recover_mt19937_cipher_seed,
+    };
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+
+    // verify that encryption & decryption with the MT19937 stream cipher work as expected
+    let msg = b"Testing that things thing works...";
+    let encrypted_msg = mt19937_cipher(5489, msg);
+    assert_ne!(encrypted_msg, msg.to_vec());
+    assert_eq!(mt19937_cipher(5489, &encrypted_msg), msg.to_vec());
+
+    let seed = rng.gen::<u16>();
+    let known_plaintext = b"AAAAAAAAAAAAAA";
+
+    let mut plaintext: Vec<u8> = (0..rng.gen_range(5, 50)).map(|_| rng.gen::<u8>()).collect();
+    plaintext.extend(known_plaintext);
+
+    let cyphertext = mt19937_cipher(seed, &plaintext);
+
+    assert_eq!(
+        recover_mt19937_cipher_seed(&cyphertext, known_plaintext),
+        Some(seed)
+    );
+
+    let clock = MockClock::new(1_571_000_000);
+
+    let token = password_reset_token(clock.now());
+    clock.sleep(rng.gen_range(0, 300));
+    assert!(is_time_seeded_token(&token, &clock, 600));
+
+    let random_token: Vec<u8> = (0..16).map(|_| rng.gen::<u8>()).collect();
+    assert!(!is_time_seeded_token(&random_token, &clock, 600));
+}
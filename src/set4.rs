@@ -0,0 +1,309 @@
+#[test]
+fn exercise_25() {
+    use crate::cipher::aes::{ctr_cipher, ctr_edit, ctr_keystream, ecb_decrypt};
+    use crate::crack::aes::{crack_ctr_edit, deterministic_key};
+    use crate::utils::read_and_decode_base64_file;
+    use rand::Rng;
+
+    let key = deterministic_key(16, 2525);
+    let nonce = rand::thread_rng().gen::<u64>();
+
+    // verify that the keystream can be picked up at any offset
+    let keystream = ctr_keystream(&key, nonce, 0, 100);
+    for offset in 0..50 {
+        assert_eq!(
+            ctr_keystream(&key, nonce, offset, 50),
+            keystream[offset..offset + 50].to_vec()
+        );
+    }
+
+    let ecb_cyphertext = read_and_decode_base64_file("data/7.txt").unwrap();
+    let plaintext = ecb_decrypt(b"YELLOW SUBMARINE", &ecb_cyphertext).unwrap();
+
+    let cyphertext = ctr_cipher(&key, nonce, &plaintext);
+
+    // verify that editing works as expected, including past the end of the cyphertext
+    let edited = ctr_edit(&key, nonce, &cyphertext, 33, b"Vanilla Ice").unwrap();
+    assert_eq!(&ctr_cipher(&key, nonce, &edited)[33..44], b"Vanilla Ice");
+    assert_eq!(edited.len(), cyphertext.len());
+    let extended = ctr_edit(&key, nonce, &cyphertext, cyphertext.len() - 2, b"!!!!").unwrap();
+    assert_eq!(extended.len(), cyphertext.len() + 2);
+    let appended = ctr_edit(&key, nonce, &cyphertext, cyphertext.len(), b"!!!!").unwrap();
+    assert_eq!(appended.len(), cyphertext.len() + 4);
+    assert!(ctr_edit(&key, nonce, &cyphertext, cyphertext.len() + 1, b"!!!!").is_none());
+
+    // the attacker only gets to call edit, never to see the key or nonce
+    let edit = |cyphertext: &[u8], offset: usize, newtext: &[u8]| {
+        ctr_edit(&key, nonce, cyphertext, offset, newtext).unwrap()
+    };
+
+    let cracked_bytes = crack_ctr_edit(&cyphertext, edit);
+
+    assert_eq!(cracked_bytes, plaintext);
+}
+
+#[test]
+fn exercise_26() {
+    use crate::crack::aes::deterministic_key;
+    use crate::crack::user_data::make_admin_user_data;
+    use crate::user_data::{CtrUserData, UserDataOracle};
+    use rand::Rng;
+
+    let key = deterministic_key(16, 2626);
+    let nonce = rand::thread_rng().gen::<u64>();
+
+    let oracle = CtrUserData::new(&key, nonce);
+
+    // verify that the oracle escapes attempts to inject the admin role directly
+    let encrypted_user = oracle.encrypt_user_data(";admin=true;").unwrap();
+    assert!(!oracle.is_admin(&encrypted_user).unwrap());
+
+    let bitflipped = make_admin_user_data(&oracle).unwrap();
+
+    assert!(oracle.is_admin(&bitflipped).unwrap());
+}
+
+#[test]
+fn exercise_27() {
+    use crate::crack::aes::{crack_cbc_key_as_iv, deterministic_key};
+    use crate::user_data::{KeyAsIvUserData, UserDataOracle};
+
+    let key = deterministic_key(16, 2727);
+
+    let oracle = KeyAsIvUserData::new(&key);
+
+    // verify that the oracle accepts its own (ASCII-only) cyphertexts
+    let cyphertext = oracle.encrypt_user_data("Wu-Tang Clan").unwrap();
+    assert!(!oracle.is_admin(&cyphertext).unwrap());
+
+    let recovered_key = crack_cbc_key_as_iv(&cyphertext, |c| oracle.is_admin(c)).unwrap();
+
+    assert_eq!(recovered_key, key);
+}
+
+#[test]
+fn sha1_fips_180_vectors() {
+    use crate::hash::sha1::{padding, sha1, state_from_digest, Sha1};
+    use crate::hash::MerkleDamgard;
+
+    assert_eq!(
+        hex::encode(sha1(b"")),
+        "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+    );
+    assert_eq!(
+        hex::encode(sha1(b"abc")),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+    assert_eq!(
+        hex::encode(sha1(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+    );
+    assert_eq!(
+        hex::encode(sha1(&vec![b'a'; 1_000_000])),
+        "34aa973cd4c4daa4f61eeb2bdbad27316534016f"
+    );
+
+    // verify that hashing in pieces, or resuming from a saved state, gives the same result
+    let msg = b"Rollin' in my 5.0 with my rag-top down so my hair can blow, \
+                the girlies on standby waving just to say hi";
+
+    let mut hasher = Sha1::new();
+    for chunk in msg.chunks(7) {
+        hasher.update(chunk);
+    }
+    assert_eq!(hasher.digest(), sha1(msg));
+
+    let mut hasher = Sha1::new();
+    hasher.update(&msg[..64]);
+    let mut resumed = Sha1::from_state(hasher.state, 64);
+    resumed.update(&msg[64..]);
+    assert_eq!(resumed.digest(), sha1(msg));
+
+    let mut hasher = Sha1::new();
+    hasher.update(b"abc");
+    hasher.update(&padding(3));
+    assert_eq!(state_from_digest(&sha1(b"abc")), hasher.state);
+}
+
+#[test]
+fn exercise_28() {
+    use crate::crack::aes::deterministic_key;
+    use crate::hash::sha1::Sha1;
+    use crate::mac::secret_prefix::{secret_prefix_mac, verify_secret_prefix_mac};
+
+    let key = deterministic_key(16, 2828);
+    let msg = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+
+    let mac = secret_prefix_mac::<Sha1>(&key, msg);
+    assert!(verify_secret_prefix_mac::<Sha1>(&key, msg, &mac));
+
+    // tampering with the message, or not knowing the key, invalidates the MAC
+    let mut tampered_msg = msg.to_vec();
+    tampered_msg[0] ^= 1;
+    assert!(!verify_secret_prefix_mac::<Sha1>(&key, &tampered_msg, &mac));
+    assert_ne!(secret_prefix_mac::<Sha1>(b"", msg), mac);
+}
+
+#[test]
+fn exercise_29() {
+    use crate::crack::aes::deterministic_key;
+    use crate::crack::mac::forge_secret_prefix_mac;
+    use crate::hash::sha1::Sha1;
+    use crate::mac::secret_prefix::{secret_prefix_mac, verify_secret_prefix_mac};
+    use rand::Rng;
+
+    let key_len = rand::thread_rng().gen_range(1, 64);
+    let key = deterministic_key(key_len, 2929);
+
+    let msg = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+    let mac = secret_prefix_mac::<Sha1>(&key, msg);
+
+    let verify = |msg: &[u8], mac: &[u8]| verify_secret_prefix_mac::<Sha1>(&key, msg, mac);
+
+    let (forged_msg, forged_mac) =
+        forge_secret_prefix_mac::<Sha1>(msg, &mac, b";admin=true", 64, verify).unwrap();
+
+    assert!(forged_msg.starts_with(msg));
+    assert!(forged_msg.ends_with(b";admin=true"));
+    assert!(verify_secret_prefix_mac::<Sha1>(
+        &key,
+        &forged_msg,
+        &forged_mac
+    ));
+}
+
+#[test]
+fn md4_rfc_1320_vectors() {
+    use crate::hash::md4::{md4, Md4};
+    use crate::hash::MerkleDamgard;
+
+    let vectors: Vec<(&[u8], &str)> = vec![
+        (b"", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+        (b"a", "bde52cb31de33e46245e05fbdbd6fb24"),
+        (b"abc", "a448017aaf21d8525fc10ae87aa6729d"),
+        (b"message digest", "d9130a8164549fe818874806e1c7014b"),
+        (
+            b"abcdefghijklmnopqrstuvwxyz",
+            "d79e1c308aa5bbcdeea8ed63df412da9",
+        ),
+        (
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+            "043f8582f241db351ce627e153e7f0e4",
+        ),
+        (
+            b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+            "e33b4ddc9c38f2199c3e7b164fcc0536",
+        ),
+    ];
+
+    for (msg, digest) in vectors {
+        assert_eq!(hex::encode(md4(msg)), digest);
+    }
+
+    // verify that resuming from a saved state gives the same result
+    let msg = b"12345678901234567890123456789012345678901234567890123456789012345678901234567890";
+    let mut hasher = Md4::new();
+    hasher.update(&msg[..64]);
+    let mut resumed = Md4::from_state(hasher.state, 64);
+    resumed.update(&msg[64..]);
+    assert_eq!(resumed.digest(), md4(msg));
+}
+
+#[test]
+fn exercise_30() {
+    use crate::crack::aes::deterministic_key;
+    use crate::crack::mac::forge_secret_prefix_mac;
+    use crate::hash::md4::Md4;
+    use crate::mac::secret_prefix::{secret_prefix_mac, verify_secret_prefix_mac};
+    use rand::Rng;
+
+    let key_len = rand::thread_rng().gen_range(1, 64);
+    let key = deterministic_key(key_len, 3030);
+
+    let msg = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+    let mac = secret_prefix_mac::<Md4>(&key, msg);
+
+    let verify = |msg: &[u8], mac: &[u8]| verify_secret_prefix_mac::<Md4>(&key, msg, mac);
+
+    let (forged_msg, forged_mac) =
+        forge_secret_prefix_mac::<Md4>(msg, &mac, b";admin=true", 64, verify).unwrap();
+
+    assert!(forged_msg.starts_with(msg));
+    assert!(forged_msg.ends_with(b";admin=true"));
+    assert!(verify_secret_prefix_mac::<Md4>(
+        &key,
+        &forged_msg,
+        &forged_mac
+    ));
+}
+
+#[test]
+fn exercise_31() {
+    use crate::crack::aes::deterministic_key;
+    use crate::crack::timing::time_signature_request;
+    use crate::hash::sha1::Sha1;
+    use crate::hmac_server::HmacServer;
+    use crate::mac::hmac::hmac;
+    use std::time::Duration;
+
+    // RFC 2202 test vectors
+    assert_eq!(
+        hex::encode(hmac::<Sha1>(&[0x0b; 20], b"Hi There")),
+        "b617318655057264e28bc0b6fb378c8ef146be00"
+    );
+    assert_eq!(
+        hex::encode(hmac::<Sha1>(b"Jefe", b"what do ya want for nothing?")),
+        "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+    );
+    assert_eq!(
+        hex::encode(hmac::<Sha1>(
+            &[0xaa; 80],
+            b"Test Using Larger Than Block-Size Key - Hash Key First"
+        )),
+        "aa4ae5e15272d00e95705637ce8a3b55ed402112"
+    );
+
+    let key = deterministic_key(16, 3131);
+    let signature = hmac::<Sha1>(&key, b"foo");
+
+    // a MAC can't be truncated to more than the whole HMAC
+    assert!(HmacServer::start(&key, 21, Duration::from_millis(5)).is_err());
+
+    let server = HmacServer::start(&key, 20, Duration::from_millis(5)).unwrap();
+    let (is_valid, _) = time_signature_request(server.addr(), "foo", &signature).unwrap();
+    assert!(is_valid);
+    let (is_valid, _) = time_signature_request(server.addr(), "foo", &[0; 20]).unwrap();
+    assert!(!is_valid);
+
+    // the more leading bytes are right, the longer the server takes to say no
+    let mut wrong_signature = signature.clone();
+    wrong_signature[10] ^= 1;
+    let (is_valid, elapsed) =
+        time_signature_request(server.addr(), "foo", &wrong_signature).unwrap();
+    assert!(!is_valid);
+    assert!(elapsed >= Duration::from_millis(50));
+}
+
+#[test]
+fn exercise_32() {
+    use crate::crack::aes::deterministic_key;
+    use crate::crack::timing::crack_signature_timing;
+    use crate::hash::sha1::Sha1;
+    use crate::hmac_server::HmacServer;
+    use crate::mac::hmac::hmac;
+    use std::time::Duration;
+
+    let key = deterministic_key(16, 3131);
+    let signature = hmac::<Sha1>(&key, b"foo");
+
+    // cracking every byte of a full-length signature takes a while, as each
+    // guess has to wait out the comparison of all the bytes before it, so
+    // crack a 32-bit truncated HMAC instead (as used by e.g. SRTP), with a
+    // smaller leak per byte than exercise 31's
+    let server = HmacServer::start(&key, 4, Duration::from_millis(1)).unwrap();
+    let cracked_signature = crack_signature_timing(server.addr(), "foo", 4, 2).unwrap();
+
+    assert_eq!(cracked_signature, &signature[..4]);
+}
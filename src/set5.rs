@@ -0,0 +1,291 @@
+use crate::protocol::transport::{connection, Endpoint};
+use std::thread;
+
+#[test]
+fn exercise_33() {
+    use crate::pubkey::dh::{decrypt_message, encrypt_message, DhGroup};
+    use crate::pubkey::math::modexp;
+    use num_bigint::BigUint;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(3333);
+
+    // toy group first
+    let group = DhGroup::new(BigUint::from(37u32), BigUint::from(5u32));
+    let alice = group.generate_keypair(&mut rng);
+    let bob = group.generate_keypair(&mut rng);
+
+    assert_eq!(
+        group.shared_secret(&alice, &bob.public),
+        group.shared_secret(&bob, &alice.public)
+    );
+
+    let group = DhGroup::nist();
+
+    // spot check modexp against the bignum library's
+    let exponent = BigUint::from(3u32).pow(500);
+    assert_eq!(
+        modexp(&group.g, &exponent, &group.p),
+        group.g.modpow(&exponent, &group.p)
+    );
+
+    let alice = group.generate_keypair(&mut rng);
+    let bob = group.generate_keypair(&mut rng);
+
+    let alice_secret = group.shared_secret(&alice, &bob.public);
+    let bob_secret = group.shared_secret(&bob, &alice.public);
+    assert_eq!(alice_secret, bob_secret);
+
+    let msg = b"Diffie and Hellman, sitting in a tree";
+    let cyphertext = encrypt_message(&alice_secret, msg, &mut rng).unwrap();
+    assert_eq!(
+        decrypt_message(&bob_secret, &cyphertext).unwrap(),
+        msg.to_vec()
+    );
+}
+
+#[test]
+fn exercise_34() {
+    use crate::crack::dh::key_fixing_mitm;
+    use crate::protocol::dh_echo::{echo_bot, initiator};
+    use crate::pubkey::dh::DhGroup;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let group = DhGroup::nist();
+    let msgs: Vec<&[u8]> = vec![b"Hi Bob, it's Alice", b"Let's meet at the usual place"];
+
+    // without an attacker
+    let (a, b) = connection();
+    thread::scope(|scope| {
+        scope.spawn(move || echo_bot(b, &mut StdRng::seed_from_u64(3402)).unwrap());
+        initiator(a, &group, &msgs, &mut StdRng::seed_from_u64(3401)).unwrap();
+    });
+
+    // with M fixing the shared secret. A and B still get along fine
+    let (a, m_a) = connection();
+    let (m_b, b) = connection();
+    let intercepted = thread::scope(|scope| {
+        scope.spawn(move || echo_bot(b, &mut StdRng::seed_from_u64(3402)).unwrap());
+        let mallory = scope.spawn(move || key_fixing_mitm(m_a, m_b).unwrap());
+        initiator(a, &group, &msgs, &mut StdRng::seed_from_u64(3401)).unwrap();
+        mallory.join().unwrap()
+    });
+
+    assert_eq!(intercepted, msgs);
+}
+
+#[test]
+fn exercise_35() {
+    use crate::crack::dh::{malicious_g_mitm, MaliciousG};
+    use crate::protocol::dh_echo::{echo_bot, initiator};
+    use crate::pubkey::dh::DhGroup;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let group = DhGroup::nist();
+    let msgs: Vec<&[u8]> = vec![b"Hi Bob, it's Alice", b"Let's meet at the usual place"];
+
+    for &malicious_g in &[MaliciousG::One, MaliciousG::P, MaliciousG::PMinusOne] {
+        // enough seeds that g = p - 1 should hit both the s = 1
+        // and s = p - 1 cases
+        for seed in 0..4 {
+            let (a, m_a) = connection();
+            let (m_b, b) = connection();
+
+            let intercepted = thread::scope(|scope| {
+                scope.spawn(move || echo_bot(b, &mut StdRng::seed_from_u64(seed)).unwrap());
+                let mallory = scope.spawn(move || malicious_g_mitm(m_a, m_b, malicious_g).unwrap());
+                initiator(a, &group, &msgs, &mut StdRng::seed_from_u64(100 + seed)).unwrap();
+                mallory.join().unwrap()
+            });
+
+            assert_eq!(intercepted, msgs);
+        }
+    }
+}
+
+#[test]
+fn sha256_fips_180_vectors() {
+    use crate::hash::sha256::{sha256, Sha256};
+    use crate::mac::hmac::hmac;
+
+    assert_eq!(
+        hex::encode(sha256(b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        hex::encode(sha256(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex::encode(sha256(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+    assert_eq!(
+        hex::encode(sha256(&vec![b'a'; 1_000_000])),
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+    );
+
+    // RFC 4231 test case 1
+    assert_eq!(
+        hex::encode(hmac::<Sha256>(&[0x0b; 20], b"Hi There")),
+        "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+    );
+}
+
+// run `server` and `client` on either end of a connection, returning
+// what each of them made of it
+fn run_login<T: Send, C, S: Send>(
+    server: impl FnOnce(Endpoint<T>) -> S + Send,
+    client: impl FnOnce(Endpoint<T>) -> C,
+) -> (C, S) {
+    let (client_conn, server_conn) = connection();
+
+    thread::scope(|scope| {
+        let handle = scope.spawn(move || server(server_conn));
+        let client_result = client(client_conn);
+        (client_result, handle.join().unwrap())
+    })
+}
+
+#[test]
+fn exercise_36() {
+    use crate::protocol::srp::{login, SrpServer};
+    use crate::pubkey::dh::DhGroup;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(3636);
+    let group = DhGroup::nist();
+    let server = SrpServer::new(group.clone(), "alice@example.com", "hunter2", &mut rng);
+
+    for &(password, should_succeed) in &[("hunter2", true), ("hunter3", false)] {
+        let (client_ok, server_ok) = run_login(
+            |conn| server.serve(conn, &mut StdRng::seed_from_u64(1)).unwrap(),
+            |conn| {
+                let mut rng = StdRng::seed_from_u64(2);
+                login(conn, &group, "alice@example.com", password, &mut rng).unwrap()
+            },
+        );
+
+        assert_eq!(client_ok, should_succeed);
+        assert_eq!(server_ok, should_succeed);
+    }
+}
+
+#[test]
+fn exercise_37() {
+    use crate::crack::srp::zero_key_login;
+    use crate::protocol::srp::SrpServer;
+    use crate::pubkey::dh::DhGroup;
+    use num_bigint::BigUint;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(3737);
+    let group = DhGroup::nist();
+    let server = SrpServer::new(group.clone(), "alice@example.com", "hunter2", &mut rng);
+
+    for multiple in 0u32..3 {
+        let forged_public = &group.p * BigUint::from(multiple);
+        let (client_ok, server_ok) = run_login(
+            |conn| server.serve(conn, &mut StdRng::seed_from_u64(1)).unwrap(),
+            |conn| zero_key_login(conn, "alice@example.com", forged_public).unwrap(),
+        );
+
+        assert!(client_ok);
+        assert!(server_ok);
+    }
+}
+
+#[test]
+fn exercise_38() {
+    use crate::crack::srp::{crack_simple_srp_password, mitm_simple_srp};
+    use crate::protocol::simple_srp::{login, SimpleSrpServer};
+    use crate::pubkey::dh::DhGroup;
+    use crate::utils::read_lines;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(3838);
+    let group = DhGroup::nist();
+    let email = "alice@example.com";
+    let password = "swordfish";
+
+    // the honest version works
+    let server = SimpleSrpServer::new(group.clone(), email, password, &mut rng);
+    let (client_ok, server_ok) = run_login(
+        |conn| server.serve(conn, &mut StdRng::seed_from_u64(1)).unwrap(),
+        |conn| login(conn, &group, email, password, &mut rng).unwrap(),
+    );
+    assert!(client_ok);
+    assert!(server_ok);
+
+    // but a client that talks to M instead gives away enough to crack its password
+    let (client_ok, captured) = run_login(
+        |conn| mitm_simple_srp(conn, &group).unwrap(),
+        |conn| login(conn, &group, email, password, &mut rng).unwrap(),
+    );
+    assert!(!client_ok);
+
+    let dictionary = read_lines("data/38.txt").unwrap();
+    assert_eq!(
+        crack_simple_srp_password(&group, &captured, &dictionary),
+        Some(password.to_string())
+    );
+}
+
+#[test]
+fn exercise_39() {
+    use crate::pubkey::math::{invmod, nth_root};
+    use crate::pubkey::rsa::deterministic_keypair;
+    use num_bigint::BigUint;
+
+    assert_eq!(
+        invmod(&BigUint::from(17u32), &BigUint::from(3120u32)),
+        Some(BigUint::from(2753u32))
+    );
+    assert_eq!(invmod(&BigUint::from(6u32), &BigUint::from(9u32)), None);
+
+    let n = BigUint::from(123_456_789u32);
+    assert_eq!(nth_root(&n.pow(3), 3), n);
+    assert_eq!(nth_root(&(n.pow(3) - 1u32), 3), &n - 1u32);
+
+    let keypair = deterministic_keypair(1024, 3, 3939);
+    assert_eq!(keypair.public.n.bits(), 1024);
+    assert_eq!(deterministic_keypair(1024, 3, 3939).public, keypair.public);
+
+    let m = BigUint::from(42u32);
+    assert_eq!(keypair.private.decrypt(&keypair.public.encrypt(&m)), m);
+
+    let msg = b"Who's that knocking at my door?";
+    let cyphertext = keypair.public.encrypt_bytes(msg);
+    assert_eq!(cyphertext.len(), 128);
+    assert_eq!(keypair.private.decrypt_bytes(&cyphertext), msg.to_vec());
+}
+
+#[test]
+fn exercise_40() {
+    use crate::crack::rsa::hastad_broadcast;
+    use crate::pubkey::rsa::deterministic_keypair;
+    use num_bigint::BigUint;
+
+    let msg = b"Attack at dawn, all three of you";
+    let m = BigUint::from_bytes_be(msg);
+
+    let cyphertexts: Vec<_> = (0..3)
+        .map(|seed| {
+            let public = deterministic_keypair(1024, 3, 4000 + seed).public;
+            (public.encrypt(&m), public)
+        })
+        .collect();
+
+    assert_eq!(
+        hastad_broadcast(&cyphertexts).unwrap().to_bytes_be(),
+        msg.to_vec()
+    );
+}
@@ -0,0 +1,236 @@
+#[test]
+fn exercise_41() {
+    use crate::crack::rsa::recover_unpadded_message;
+    use crate::pubkey::rsa::deterministic_keypair;
+    use crate::rsa_oracle::{DecryptionOracle, OnceOnlyDecryptionServer};
+    use num_bigint::BigUint;
+
+    let server = OnceOnlyDecryptionServer::new(deterministic_keypair(1024, 65537, 4141));
+
+    let msg = br#"{"time": 1356304276, "social": "555-55-5555"}"#;
+    let cyphertext = server.public_key().encrypt(&BigUint::from_bytes_be(msg));
+
+    // the server has already decrypted the message once, for its rightful owner
+    let oracle: &dyn DecryptionOracle = &server;
+    assert_eq!(
+        oracle.decrypt(&cyphertext).unwrap().to_bytes_be(),
+        msg.to_vec()
+    );
+    assert!(oracle.decrypt(&cyphertext).is_err());
+
+    let recovered =
+        recover_unpadded_message(oracle, &cyphertext, &BigUint::from(31337u32)).unwrap();
+    assert_eq!(recovered.to_bytes_be(), msg.to_vec());
+}
+
+#[test]
+fn exercise_42() {
+    use crate::crack::rsa::forge_pkcs1_signature;
+    use crate::pubkey::pkcs1::{sign, verify_sloppy, verify_strict};
+    use crate::pubkey::rsa::deterministic_keypair;
+
+    let keypair = deterministic_keypair(1024, 3, 4242);
+    let msg = b"hi mom";
+
+    let signature = sign(&keypair.private, msg);
+    assert!(verify_sloppy(&keypair.public, msg, &signature));
+    assert!(verify_strict(&keypair.public, msg, &signature));
+    assert!(!verify_sloppy(&keypair.public, b"hi dad", &signature));
+
+    let forged_signature = forge_pkcs1_signature(&keypair.public, msg).unwrap();
+    assert!(verify_sloppy(&keypair.public, msg, &forged_signature));
+    assert!(!verify_strict(&keypair.public, msg, &forged_signature));
+}
+
+#[test]
+fn exercise_43() {
+    use crate::crack::dsa::brute_force_nonce;
+    use crate::hash::sha1::sha1;
+    use crate::pubkey::dsa::{message_hash, DsaParams, DsaSignature};
+    use num_bigint::BigUint;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(4343);
+
+    // fresh parameters are sound
+    let params = DsaParams::generate(512, 160, &mut rng);
+    assert!(params.is_valid(&mut rng));
+    let keypair = params.generate_keypair(&mut rng);
+    let signature = params.sign(&keypair.x, b"hello, world", &mut rng);
+    assert!(params.verify(&keypair.y, b"hello, world", &signature));
+    assert!(!params.verify(&keypair.y, b"goodbye, world", &signature));
+
+    let params = DsaParams::standard();
+    assert!(params.is_valid(&mut rng));
+
+    let y = BigUint::parse_bytes(
+        b"84ad4719d044495496a3201c8ff484feb45b962e7302e56a392aee4abab3e4bdebf2955b4736012f21a08084\
+          056b19bcd7fee56048e004e44984e2f411788efdc837a0d2e5abb7b555039fd243ac01f0fb2ed1dec568280c\
+          e678e931868d23eb095fde9d3779191b8c0299d6e07bbb283e6633451e535c45513b2d33c99ea17",
+        16,
+    )
+    .unwrap();
+    let msg = b"For those that envy a MC it can be hazardous to your health\n\
+                So be friendly, a matter of life and death, just like a etch-a-sketch\n";
+    assert_eq!(
+        hex::encode(sha1(msg)),
+        "d2d0714f014a9784047eaeccf956520045c45265"
+    );
+
+    let signature = DsaSignature {
+        r: BigUint::parse_bytes(b"548099063082341131477253921760299949438196259240", 10).unwrap(),
+        s: BigUint::parse_bytes(b"857042759984254168557880549501802188789837994940", 10).unwrap(),
+    };
+    assert!(params.verify(&y, msg, &signature));
+
+    let x = brute_force_nonce(&params, &y, &message_hash(msg), &signature, 1 << 16).unwrap();
+    assert_eq!(
+        hex::encode(sha1(x.to_str_radix(16).as_bytes())),
+        "0954edd5e0afe5542a4adf012611a91912a3ec16"
+    );
+}
+
+#[test]
+fn exercise_44() {
+    use crate::crack::dsa::{read_signed_messages, x_from_repeated_nonce};
+    use crate::hash::sha1::sha1;
+    use crate::pubkey::dsa::DsaParams;
+    use num_bigint::BigUint;
+
+    let params = DsaParams::standard();
+    let y = BigUint::parse_bytes(
+        b"29fb1f3ada81fe1a1244b039321bdf42fa8e28d0056d4ceb9d0cca48db7905e679b7cba655023904a73bd05d\
+          3680da41ca39ba1a9bfb6a9f351d07572f9dc79f0b4457a404bb2c7cbfbac9711e8ac19a53b2780f17706567\
+          9ef6fb892bac3cb2ec94a3206e1b17a0765a867c0465a59c003eded5e325f0ae2291ad002a8da3f3",
+        16,
+    )
+    .unwrap();
+
+    let signed_messages = read_signed_messages("data/44.txt").unwrap();
+    for signed in &signed_messages {
+        assert!(params.verify(&y, signed.msg.as_bytes(), &signed.signature));
+    }
+
+    let x = x_from_repeated_nonce(&params, &y, &signed_messages).unwrap();
+    assert_eq!(
+        hex::encode(sha1(x.to_str_radix(16).as_bytes())),
+        "8652980914135b1b31673af05f59998d010f1f93"
+    );
+}
+
+#[test]
+fn exercise_45() {
+    use crate::crack::dsa::magic_signature;
+    use crate::pubkey::dsa::DsaParams;
+    use num_bigint::BigUint;
+    use num_traits::Zero;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(4545);
+    let standard = DsaParams::standard();
+    let keypair = standard.generate_keypair(&mut rng);
+
+    // g = 0: every signature has r = 0, and a verifier that doesn't
+    // check r accepts r = 0 for anything
+    let params = DsaParams {
+        g: BigUint::zero(),
+        ..standard.clone()
+    };
+    let signature = params.sign(&keypair.x, b"Hello, world", &mut rng);
+    assert!(signature.r.is_zero());
+    assert!(params.verify_unchecked(&keypair.y, b"Goodbye, world", &signature));
+    assert!(!params.verify(&keypair.y, b"Goodbye, world", &signature));
+
+    // g = p + 1: the magic signature verifies for any string, even with all the checks
+    let params = DsaParams {
+        g: &standard.p + 1u32,
+        ..standard.clone()
+    };
+    let signature = magic_signature(&params, &keypair.y, &BigUint::from(12345u32)).unwrap();
+    assert!(params.verify(&keypair.y, b"Hello, world", &signature));
+    assert!(params.verify(&keypair.y, b"Goodbye, world", &signature));
+}
+
+#[test]
+fn exercise_46() {
+    use crate::crack::rsa::decrypt_with_parity_oracle;
+    use crate::pubkey::rsa::deterministic_keypair;
+    use crate::rsa_oracle::{ParityOracle, ParityServer};
+    use num_bigint::BigUint;
+
+    let server = ParityServer::new(deterministic_keypair(1024, 65537, 4646));
+    let msg = base64::decode(
+        "VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==",
+    )
+    .unwrap();
+    let cyphertext = server.public_key().encrypt(&BigUint::from_bytes_be(&msg));
+
+    // the upper bound only ever comes down
+    let mut steps = 0;
+    let mut last_bound = server.public_key().n.clone();
+    let mut progress = |bound: &BigUint| {
+        assert!(*bound <= last_bound);
+        last_bound = bound.clone();
+        steps += 1;
+    };
+
+    let plaintext = decrypt_with_parity_oracle(&server, &cyphertext, Some(&mut progress));
+
+    assert_eq!(plaintext.to_bytes_be(), msg);
+    assert_eq!(steps, 1024);
+}
+
+#[test]
+fn exercise_47() {
+    use crate::crack::rsa::bleichenbacher;
+    use crate::pubkey::pkcs1::{decrypt, encrypt, unpad_encryption_block};
+    use crate::pubkey::rsa::deterministic_keypair;
+    use crate::rsa_oracle::{PaddingOracle, PaddingServer};
+    use num_bigint::BigUint;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(4747);
+    let keypair = deterministic_keypair(256, 3, 4747);
+    let msg = b"kick it, CC";
+
+    let cyphertext = encrypt(&keypair.public, msg, &mut rng).unwrap();
+    assert_eq!(
+        decrypt(&keypair.private, &cyphertext).unwrap(),
+        msg.to_vec()
+    );
+
+    let server = PaddingServer::new(keypair);
+    let cyphertext = BigUint::from_bytes_be(&cyphertext);
+    assert!(server.is_conforming(&cyphertext));
+
+    let result = bleichenbacher(&server, &cyphertext).unwrap();
+
+    assert_eq!(unpad_encryption_block(&result.block).unwrap(), msg.to_vec());
+    assert!(result.oracle_queries > 0);
+}
+
+#[test]
+fn exercise_48() {
+    use crate::crack::rsa::bleichenbacher;
+    use crate::pubkey::pkcs1::{encrypt, unpad_encryption_block};
+    use crate::pubkey::rsa::deterministic_keypair;
+    use crate::rsa_oracle::PaddingServer;
+    use num_bigint::BigUint;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(4848);
+    let keypair = deterministic_keypair(768, 3, 4848);
+    let msg = b"kick it, CC";
+
+    let cyphertext = encrypt(&keypair.public, msg, &mut rng).unwrap();
+    let server = PaddingServer::new(keypair);
+
+    let result = bleichenbacher(&server, &BigUint::from_bytes_be(&cyphertext)).unwrap();
+
+    assert_eq!(unpad_encryption_block(&result.block).unwrap(), msg.to_vec());
+    assert!(result.oracle_queries > 0);
+}
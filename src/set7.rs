@@ -0,0 +1,172 @@
+#[test]
+fn exercise_49() {
+    use crate::crack::aes::deterministic_key;
+    use crate::crack::transfer::{forge_multi_transfer, forge_transfer_from};
+    use crate::transfer::{BankClient, BankServer, MultiTransfer, Transfer};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng: StdRng = SeedableRng::seed_from_u64(4949);
+    let key = deterministic_key(16, 4949);
+    let server = BankServer::new(&key);
+
+    let (victim, attacker) = (13, 66);
+
+    // the server carries out transfers signed by the web client, and
+    // rejects ones that have been tampered with
+    let request = BankClient::new(&key, victim).transfer_request(7, 250, &mut rng);
+    assert_eq!(
+        server.process_transfer(&request).unwrap(),
+        Transfer {
+            from: victim,
+            to: 7,
+            amount: 250
+        }
+    );
+    let mut tampered = request.clone();
+    tampered[0] ^= 1;
+    assert!(server.process_transfer(&tampered).is_err());
+
+    // with the IV in the attacker's hands, a transfer between their own
+    // accounts can be turned into one out of the victim's
+    let attacker_client = BankClient::new(&key, attacker);
+    let request = attacker_client.transfer_request(attacker, 1_000_000, &mut rng);
+    let forged = forge_transfer_from(&request, victim).unwrap();
+
+    assert_eq!(
+        server.process_transfer(&forged).unwrap(),
+        Transfer {
+            from: victim,
+            to: attacker,
+            amount: 1_000_000
+        }
+    );
+
+    // with a fixed IV, the attacker splices their own transactions onto
+    // a request captured from the victim
+    let victim_request = BankClient::new(&key, victim).multi_transfer_request(&[(7, 250), (8, 40)]);
+    assert_eq!(
+        server.process_multi_transfer(&victim_request).unwrap(),
+        MultiTransfer {
+            from: victim,
+            tx_list: vec![(7, 250), (8, 40)]
+        }
+    );
+
+    let attacker_request = attacker_client.multi_transfer_request(&[(0, 0), (attacker, 1_000_000)]);
+    let forged = forge_multi_transfer(&victim_request, &attacker_request).unwrap();
+
+    let transfer = server.process_multi_transfer(&forged).unwrap();
+    assert_eq!(transfer.from, victim);
+    assert!(transfer.tx_list.contains(&(attacker, 1_000_000)));
+}
+
+#[test]
+fn exercise_50() {
+    use crate::crack::mac::forge_cbc_mac_collision;
+    use crate::mac::cbc_mac::fixed_iv_cbc_mac;
+
+    let key = b"YELLOW SUBMARINE";
+    let snippet = b"alert('MZA who was that?');\n";
+
+    let hash = fixed_iv_cbc_mac(key, snippet).unwrap();
+    assert_eq!(hex::encode(&hash), "296b8d7cb78a243dda4d0a61d33bbdd1");
+
+    // the glue block goes in a line comment, so must be printable and
+    // mustn't contain a line break
+    let is_printable = |bytes: &[u8]| bytes.iter().all(|&b| b == b' ' || b.is_ascii_graphic());
+    let forged = forge_cbc_mac_collision(
+        key,
+        &[0; 16],
+        snippet,
+        b"alert('Ayo, the Wu is back!');//",
+        is_printable,
+        1 << 28,
+    )
+    .unwrap();
+
+    assert_eq!(fixed_iv_cbc_mac(key, &forged).unwrap(), hash);
+
+    // the whole forgery is printable, up to the snippet's closing line break
+    let (line, line_break) = forged.split_at(forged.len() - 1);
+    assert!(line.starts_with(b"alert('Ayo, the Wu is back!');//"));
+    assert!(is_printable(line));
+    assert_eq!(line_break, b"\n");
+    assert!(forged.ends_with(&snippet[16..]));
+}
+
+#[test]
+fn exercise_51() {
+    use crate::compression_oracle::{
+        format_request, CbcCompressionClient, CompressionOracle, CtrCompressionClient,
+    };
+    use crate::crack::compression::crack_compression_oracle;
+
+    let session_id = "TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=";
+    let base64_chars = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=";
+
+    assert!(String::from_utf8(format_request(session_id, b"foo"))
+        .unwrap()
+        .contains("Cookie: sessionid=TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=\r\n"));
+
+    // a payload that repeats part of the cookie compresses better than one that doesn't
+    let oracle = CtrCompressionClient::new(session_id);
+    assert!(
+        oracle.encrypted_len(b"sessionid=TmV2ZXIg").unwrap()
+            < oracle.encrypted_len(b"sessionid=UnV2ZXIg").unwrap()
+    );
+
+    let cracked = crack_compression_oracle(&oracle, b"sessionid=", base64_chars, b'\r', 64)
+        .unwrap()
+        .unwrap();
+    assert_eq!(cracked, session_id.as_bytes());
+
+    // with a block cipher, only changes that cross a block boundary show
+    let oracle = CbcCompressionClient::new(session_id);
+    let cracked = crack_compression_oracle(&oracle, b"sessionid=", base64_chars, b'\r', 64)
+        .unwrap()
+        .unwrap();
+    assert_eq!(cracked, session_id.as_bytes());
+}
+
+#[test]
+fn exercise_52() {
+    use crate::crack::multicollision::{cascade_collision, MultiCollision};
+    use crate::hash::toy::ToyHash;
+    use crate::hash::CompressionFunction;
+    use bit_vec::BitVec;
+    use std::collections::HashSet;
+
+    // a 2^8-way collision in a 16-bit hash, for 8 birthday searches' work
+    let f = ToyHash::new(2);
+    let multicollision = MultiCollision::new(&f, 8);
+
+    let messages: HashSet<Vec<u8>> = multicollision.messages().collect();
+    assert_eq!(messages.len(), 256);
+    assert!(messages
+        .iter()
+        .all(|msg| f.compress_blocks(&f.initial_state(), msg) == multicollision.state));
+    let digest = f.hash(&multicollision.message(&BitVec::from_elem(8, false)));
+    assert!(messages.iter().all(|msg| f.hash(msg) == digest));
+
+    // too many messages to list, but any of them can be picked out
+    let multicollision = MultiCollision::new(&f, 80);
+    let choices = BitVec::from_bytes(b"Wu-Tang!!!");
+    assert_eq!(
+        f.hash(&multicollision.message(&choices)),
+        f.hash(&multicollision.message(&BitVec::from_elem(80, true)))
+    );
+
+    // f(x) || g(x), with a 24-bit g, is barely stronger than g alone
+    let f = ToyHash::new(2);
+    let g = ToyHash::new(3);
+    let (msg_a, msg_b) = cascade_collision(&f, &g);
+
+    assert_ne!(msg_a, msg_b);
+    assert_eq!(f.hash(&msg_a), f.hash(&msg_b));
+    assert_eq!(g.hash(&msg_a), g.hash(&msg_b));
+
+    // far fewer compressions than the 2^20 a birthday search on the
+    // 40-bit cascade would need
+    assert!(f.compressions() + g.compressions() < 1 << 17);
+}
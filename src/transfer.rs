@@ -0,0 +1,211 @@
+use crate::cipher::aes::AES_BLOCK_SIZE;
+use crate::mac::cbc_mac::{cbc_mac, fixed_iv_cbc_mac, verify_cbc_mac, verify_fixed_iv_cbc_mac};
+use failure::{format_err, Error};
+use rand::Rng;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+// a single transfer, encoded as `from=1&to=2&amount=1000`
+#[derive(Debug, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: u32,
+    pub to: u32,
+    pub amount: u64,
+}
+
+// several transfers out of one account, encoded as
+// `from=1&tx_list=2:1000;3:500`
+#[derive(Debug, PartialEq, Eq)]
+pub struct MultiTransfer {
+    pub from: u32,
+    pub tx_list: Vec<(u32, u64)>,
+}
+
+impl fmt::Display for Transfer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "from={}&to={}&amount={}",
+            self.from, self.to, self.amount
+        )
+    }
+}
+
+impl fmt::Display for MultiTransfer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tx_list = self
+            .tx_list
+            .iter()
+            .map(|(to, amount)| format!("{}:{}", to, amount))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        write!(f, "from={}&tx_list={}", self.from, tx_list)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseTransferError(String);
+
+impl fmt::Display for ParseTransferError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ParseTransferError(bad_string) = self;
+
+        write!(f, "Unable to parse transfer from string: {:?}", bad_string)
+    }
+}
+
+impl error::Error for ParseTransferError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+fn parse_terms(input_str: &str) -> Vec<Vec<&str>> {
+    input_str
+        .split('&')
+        .map(|term| term.splitn(2, '=').collect())
+        .collect()
+}
+
+fn find_term<'a>(terms: &[Vec<&'a str>], key: &str) -> Option<&'a str> {
+    terms
+        .iter()
+        .find(|t| t[0] == key)
+        .and_then(|t| t.get(1))
+        .cloned()
+}
+
+impl FromStr for Transfer {
+    type Err = ParseTransferError;
+
+    fn from_str(input_str: &str) -> Result<Self, Self::Err> {
+        let terms = parse_terms(input_str);
+        let error = || ParseTransferError(input_str.to_owned());
+
+        let from = find_term(&terms, "from")
+            .and_then(|val| val.parse::<u32>().ok())
+            .ok_or_else(error)?;
+        let to = find_term(&terms, "to")
+            .and_then(|val| val.parse::<u32>().ok())
+            .ok_or_else(error)?;
+        let amount = find_term(&terms, "amount")
+            .and_then(|val| val.parse::<u64>().ok())
+            .ok_or_else(error)?;
+
+        Ok(Transfer { from, to, amount })
+    }
+}
+
+impl FromStr for MultiTransfer {
+    type Err = ParseTransferError;
+
+    // transactions in the list that don't parse are skipped over
+    fn from_str(input_str: &str) -> Result<Self, Self::Err> {
+        let terms = parse_terms(input_str);
+        let error = || ParseTransferError(input_str.to_owned());
+
+        let from = find_term(&terms, "from")
+            .and_then(|val| val.parse::<u32>().ok())
+            .ok_or_else(error)?;
+        let tx_list = find_term(&terms, "tx_list")
+            .ok_or_else(error)?
+            .split(';')
+            .filter_map(|tx| {
+                let mut parts = tx.splitn(2, ':');
+                let to = parts.next()?.parse::<u32>().ok()?;
+                let amount = parts.next()?.parse::<u64>().ok()?;
+                Some((to, amount))
+            })
+            .collect();
+
+        Ok(MultiTransfer { from, tx_list })
+    }
+}
+
+// the bank's API server, which shares a key with its web client, and
+// carries out any transfer request with a valid CBC-MAC
+pub struct BankServer {
+    key: Vec<u8>,
+}
+
+impl BankServer {
+    pub fn new(key: &[u8]) -> BankServer {
+        BankServer { key: key.to_vec() }
+    }
+
+    // message || IV || MAC
+    pub fn process_transfer(&self, request: &[u8]) -> Result<Transfer, Error> {
+        if request.len() < 2 * AES_BLOCK_SIZE {
+            return Err(format_err!("Request is too short"));
+        }
+        let (msg, iv_and_mac) = request.split_at(request.len() - 2 * AES_BLOCK_SIZE);
+        let (iv, mac) = iv_and_mac.split_at(AES_BLOCK_SIZE);
+
+        if !verify_cbc_mac(&self.key, iv, msg, mac)? {
+            return Err(format_err!("Invalid MAC"));
+        }
+
+        Ok(String::from_utf8_lossy(msg).parse::<Transfer>()?)
+    }
+
+    // message || MAC, with a fixed IV
+    pub fn process_multi_transfer(&self, request: &[u8]) -> Result<MultiTransfer, Error> {
+        if request.len() < AES_BLOCK_SIZE {
+            return Err(format_err!("Request is too short"));
+        }
+        let (msg, mac) = request.split_at(request.len() - AES_BLOCK_SIZE);
+
+        if !verify_fixed_iv_cbc_mac(&self.key, msg, mac)? {
+            return Err(format_err!("Invalid MAC"));
+        }
+
+        Ok(String::from_utf8_lossy(msg).parse::<MultiTransfer>()?)
+    }
+}
+
+// the web client, logged in as `account`, which will sign requests for
+// transfers out of that account (and no other)
+pub struct BankClient {
+    key: Vec<u8>,
+    account: u32,
+}
+
+impl BankClient {
+    pub fn new(key: &[u8], account: u32) -> BankClient {
+        BankClient {
+            key: key.to_vec(),
+            account,
+        }
+    }
+
+    pub fn transfer_request(&self, to: u32, amount: u64, rng: &mut impl Rng) -> Vec<u8> {
+        let msg = Transfer {
+            from: self.account,
+            to,
+            amount,
+        }
+        .to_string()
+        .into_bytes();
+        let iv: Vec<u8> = (0..AES_BLOCK_SIZE).map(|_| rng.gen::<u8>()).collect();
+
+        let mut request = msg.clone();
+        request.extend(&iv);
+        request.extend(cbc_mac(&self.key, &iv, &msg).unwrap());
+        request
+    }
+
+    pub fn multi_transfer_request(&self, tx_list: &[(u32, u64)]) -> Vec<u8> {
+        let msg = MultiTransfer {
+            from: self.account,
+            tx_list: tx_list.to_vec(),
+        }
+        .to_string()
+        .into_bytes();
+
+        let mut request = msg.clone();
+        request.extend(fixed_iv_cbc_mac(&self.key, &msg).unwrap());
+        request
+    }
+}
@@ -0,0 +1,109 @@
+use crate::cipher::aes::{cbc_decrypt, cbc_encrypt, ctr_cipher, CipherError};
+
+pub fn make_user(input: &str) -> String {
+    let escaped_input = input.replace(";", "%3B").replace("=", "%3D");
+    format!(
+        "comment1=cooking%20MCs;\
+         userdata={};\
+         comment2=%20like%20a%20pound%20of%20bacon",
+        escaped_input
+    )
+}
+
+pub fn has_admin_role(user_bytes: &[u8]) -> bool {
+    let user_str = String::from_utf8_lossy(user_bytes);
+
+    user_str
+        .split(';')
+        .map(|kv| {
+            let mut kv_iter = kv.splitn(2, '=');
+            (kv_iter.next(), kv_iter.next())
+        })
+        .any(|tup| tup == (Some("admin"), Some("true")))
+}
+
+// an encrypted "userdata" cookie service: it wraps attacker-supplied
+// userdata with make_user and encrypts it, and can later tell
+// whether a cookie it's handed decrypts to an admin user
+pub trait UserDataOracle {
+    fn encrypt_user_data(&self, input: &str) -> Result<Vec<u8>, CipherError>;
+    fn is_admin(&self, cyphertext: &[u8]) -> Result<bool, CipherError>;
+}
+
+pub struct CbcUserData {
+    key: Vec<u8>,
+    iv: Vec<u8>,
+}
+
+impl CbcUserData {
+    pub fn new(key: &[u8], iv: &[u8]) -> CbcUserData {
+        CbcUserData {
+            key: key.to_vec(),
+            iv: iv.to_vec(),
+        }
+    }
+}
+
+impl UserDataOracle for CbcUserData {
+    fn encrypt_user_data(&self, input: &str) -> Result<Vec<u8>, CipherError> {
+        cbc_encrypt(&self.key, &self.iv, make_user(input).as_bytes())
+    }
+
+    fn is_admin(&self, cyphertext: &[u8]) -> Result<bool, CipherError> {
+        let decrypted_bytes = cbc_decrypt(&self.key, &self.iv, cyphertext)?;
+        Ok(has_admin_role(&decrypted_bytes))
+    }
+}
+
+pub struct CtrUserData {
+    key: Vec<u8>,
+    nonce: u64,
+}
+
+impl CtrUserData {
+    pub fn new(key: &[u8], nonce: u64) -> CtrUserData {
+        CtrUserData {
+            key: key.to_vec(),
+            nonce,
+        }
+    }
+}
+
+impl UserDataOracle for CtrUserData {
+    fn encrypt_user_data(&self, input: &str) -> Result<Vec<u8>, CipherError> {
+        Ok(ctr_cipher(&self.key, self.nonce, make_user(input).as_bytes()))
+    }
+
+    fn is_admin(&self, cyphertext: &[u8]) -> Result<bool, CipherError> {
+        let decrypted_bytes = ctr_cipher(&self.key, self.nonce, cyphertext);
+        Ok(has_admin_role(&decrypted_bytes))
+    }
+}
+
+// a CBC userdata service that (unwisely) reuses its key as the IV, and
+// complains about non-ASCII plaintexts by echoing the whole plaintext back
+pub struct KeyAsIvUserData {
+    key: Vec<u8>,
+}
+
+impl KeyAsIvUserData {
+    pub fn new(key: &[u8]) -> KeyAsIvUserData {
+        KeyAsIvUserData { key: key.to_vec() }
+    }
+}
+
+impl UserDataOracle for KeyAsIvUserData {
+    fn encrypt_user_data(&self, input: &str) -> Result<Vec<u8>, CipherError> {
+        cbc_encrypt(&self.key, &self.key, make_user(input).as_bytes())
+    }
+
+    fn is_admin(&self, cyphertext: &[u8]) -> Result<bool, CipherError> {
+        let decrypted_bytes = cbc_decrypt(&self.key, &self.key, cyphertext)?;
+
+        if decrypted_bytes.iter().any(|&b| b > 127) {
+            return Err(CipherError::InvalidAscii(decrypted_bytes));
+        }
+
+        Ok(has_admin_role(&decrypted_bytes))
+    }
+}
@@ -54,6 +54,16 @@ pub fn read_and_decode_base64_lines(filename: &str) -> Result<Vec<Vec<u8>>, Erro
     Ok(data)
 }
 
+pub fn read_lines(filename: &str) -> Result<Vec<String>, Error> {
+    let f = File::open(filename)?;
+
+    let file_reader = BufReader::new(f);
+
+    let lines = file_reader.lines().collect::<Result<Vec<String>, _>>()?;
+
+    Ok(lines)
+}
+
 
 fn load_char_table(filename: &str, count_col: &str) -> Result<HashMap<char, f32>, Error> {
     let f = File::open(filename)?;